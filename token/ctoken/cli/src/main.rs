@@ -0,0 +1,756 @@
+use std::error::Error;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use ctoken::{
+    instruction,
+    recipient::RecipientFormat,
+    state::{CToken, CTokenMode, Config, Route, TransferId, CASHIER_ADDRESS_SIZE, NATIVE_MINT},
+};
+use ctoken_client::rpc;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signer},
+    system_program,
+    transaction::Transaction,
+};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_token_2022::{extension::StateWithExtensions, state::Account as TokenAccount};
+
+const TOKEN_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+/// Manages cToken program configs and cTokens
+#[derive(Parser)]
+#[command(name = "ctoken-cli", version)]
+struct Cli {
+    /// RPC URL, e.g. http://localhost:8899 for a local test validator
+    #[arg(
+        long,
+        short = 'u',
+        global = true,
+        default_value = "http://localhost:8899"
+    )]
+    url: String,
+
+    /// Keypair file paying for and signing transactions as owner, authority
+    /// or user
+    #[arg(long, short = 'k', global = true, default_value_t = default_keypair_path())]
+    keypair: String,
+
+    /// cToken program id
+    #[arg(long, global = true, env = "C_TOKEN_PROGRAM_ID")]
+    program_id: Option<Pubkey>,
+
+    /// Prints the unsigned transaction in base64 instead of sending it
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Creates or shows configs
+    #[command(subcommand)]
+    Config(ConfigCommand),
+
+    /// Creates a cToken
+    Create(CreateArgs),
+
+    /// Changes the bridge limits of a cToken
+    ChangeLimit {
+        /// cToken
+        c_token: Pubkey,
+
+        /// Max amount for bridge
+        #[arg(long)]
+        max: u64,
+
+        /// Min amount for bridge
+        #[arg(long)]
+        min: u64,
+    },
+
+    /// Changes the bridge fee in lamports of a config
+    ChangeFee {
+        /// Config
+        config: Pubkey,
+
+        /// Bridge fee in lamports
+        #[arg(long)]
+        fee: u64,
+
+        /// Fee collector, required for a non zero fee
+        #[arg(long)]
+        fee_collector: Option<Pubkey>,
+    },
+
+    /// Proposes a new owner of a config
+    TransferOwner {
+        /// Config
+        config: Pubkey,
+
+        /// Proposed owner
+        new_owner: Pubkey,
+    },
+
+    /// Bridges tokens of the keypair wallet
+    Bridge(BridgeArgs),
+
+    /// Settles a transfer with the keypair as config authority
+    Settle(SettleArgs),
+
+    /// Shows any cToken program account
+    Inspect {
+        /// Account address
+        address: Pubkey,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Creates a config with the keypair as owner
+    Init {
+        /// Seed of the config address
+        #[arg(long)]
+        seed: String,
+
+        /// Bridge fee in lamports
+        #[arg(long, default_value_t = 0)]
+        fee: u64,
+
+        /// Authority settling transfers, defaults to the keypair
+        #[arg(long)]
+        authority: Option<Pubkey>,
+
+        /// Fee collector, defaults to the keypair
+        #[arg(long)]
+        fee_collector: Option<Pubkey>,
+    },
+
+    /// Shows a config
+    Show {
+        /// Config
+        config: Pubkey,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Mode {
+    Lock,
+    Mint,
+    Native,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Evm,
+    Iotex,
+}
+
+impl From<Format> for RecipientFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Evm => RecipientFormat::Evm,
+            Format::Iotex => RecipientFormat::IoTeX,
+        }
+    }
+}
+
+impl From<Mode> for CTokenMode {
+    fn from(mode: Mode) -> Self {
+        match mode {
+            Mode::Lock => CTokenMode::Lock,
+            Mode::Mint => CTokenMode::Mint,
+            Mode::Native => CTokenMode::Native,
+        }
+    }
+}
+
+#[derive(Args)]
+struct CreateArgs {
+    /// Config
+    #[arg(long)]
+    config: Pubkey,
+
+    /// Custody mode
+    #[arg(long, value_enum)]
+    mode: Mode,
+
+    /// Token mint, ignored for native cTokens
+    #[arg(long)]
+    mint: Option<Pubkey>,
+
+    /// Vault token account of lock cTokens, owned by the cToken authority
+    #[arg(long)]
+    vault: Option<Pubkey>,
+
+    /// Token program of the mint
+    #[arg(long, default_value_t = TOKEN_PROGRAM_ID)]
+    token_program: Pubkey,
+
+    /// Destination chain id
+    #[arg(long)]
+    destination: u32,
+
+    /// Max amount for bridge
+    #[arg(long)]
+    max: u64,
+
+    /// Min amount for bridge
+    #[arg(long, default_value_t = 0)]
+    min: u64,
+
+    /// Decimals of the token on the destination chain
+    #[arg(long)]
+    remote_decimals: u8,
+
+    /// Recipient address format of the destination chain, required unless
+    /// the destination is well known
+    #[arg(long, value_enum)]
+    recipient_format: Option<Format>,
+}
+
+#[derive(Args)]
+struct BridgeArgs {
+    /// cToken
+    c_token: Pubkey,
+
+    /// Amount in mint decimals, including the token fee
+    #[arg(long)]
+    amount: u64,
+
+    /// Recipient on the destination chain
+    #[arg(long)]
+    recipient: String,
+
+    /// Destination chain id, defaults to the cToken destination
+    #[arg(long)]
+    destination: Option<u32>,
+
+    /// Token account to bridge from, defaults to the associated token account
+    /// of the keypair
+    #[arg(long)]
+    user_account: Option<Pubkey>,
+
+    /// Payload in hex
+    #[arg(long, default_value = "")]
+    payload: String,
+}
+
+#[derive(Args)]
+struct SettleArgs {
+    /// cToken
+    c_token: Pubkey,
+
+    /// Recipient token account or wallet. Wallets of lock and mint cTokens
+    /// are paid to their associated token account
+    #[arg(long)]
+    recipient: Pubkey,
+
+    /// Amount in remote decimals
+    #[arg(long)]
+    amount: u128,
+
+    /// Cashier of the source chain in hex
+    #[arg(long)]
+    cashier: String,
+
+    /// Transfer index of the cashier
+    #[arg(long)]
+    index: u64,
+}
+
+fn default_keypair_path() -> String {
+    std::env::var("HOME")
+        .map(|home| format!("{}/.config/solana/id.json", home))
+        .unwrap_or_else(|_| "id.json".to_string())
+}
+
+struct Context {
+    client: RpcClient,
+    keypair: Keypair,
+    program_id: Pubkey,
+    dry_run: bool,
+}
+
+impl Context {
+    fn program_id(&self) -> &Pubkey {
+        &self.program_id
+    }
+
+    /// Sends `instructions` signed by the keypair, or prints them unsigned
+    /// on a dry run
+    fn send(&self, instructions: &[Instruction]) -> Result<(), Box<dyn Error>> {
+        let transaction = Transaction::new_with_payer(instructions, Some(&self.keypair.pubkey()));
+        if self.dry_run {
+            for instruction in instructions {
+                println!("{:#?}", instruction);
+            }
+            println!("{}", STANDARD.encode(bincode::serialize(&transaction)?));
+            return Ok(());
+        }
+
+        let mut transaction = transaction;
+        transaction.sign(&[&self.keypair], self.client.get_latest_blockhash()?);
+        let signature = self.client.send_and_confirm_transaction(&transaction)?;
+        println!("Signature: {}", signature);
+        Ok(())
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+    let keypair = read_keypair_file(&cli.keypair)
+        .map_err(|e| format!("Failed to read keypair {}: {}", cli.keypair, e))?;
+    let context = Context {
+        client: RpcClient::new_with_commitment(cli.url, CommitmentConfig::confirmed()),
+        keypair,
+        program_id: cli.program_id.ok_or("--program-id is required")?,
+        dry_run: cli.dry_run,
+    };
+
+    match cli.command {
+        Command::Config(ConfigCommand::Init {
+            seed,
+            fee,
+            authority,
+            fee_collector,
+        }) => {
+            let owner = context.keypair.pubkey();
+            let (config, _) = Config::find_address(context.program_id(), &owner, &seed);
+            println!("Config: {}", config);
+            context.send(&[instruction::config(
+                context.program_id(),
+                &owner,
+                &authority.unwrap_or(owner),
+                &fee_collector.unwrap_or(owner),
+                &owner,
+                fee,
+                seed,
+            )?])
+        }
+        Command::Config(ConfigCommand::Show { config }) => {
+            let config = rpc::get_config(&context.client, context.program_id(), &config)?;
+            println!("{:#?}", config);
+            Ok(())
+        }
+        Command::Create(args) => create(&context, args),
+        Command::ChangeLimit { c_token, max, min } => {
+            let state = rpc::get_c_token(&context.client, context.program_id(), &c_token)?;
+            context.send(&[instruction::change_limit(
+                context.program_id(),
+                &state.config,
+                &c_token,
+                &context.keypair.pubkey(),
+                max,
+                min,
+            )?])
+        }
+        Command::ChangeFee {
+            config,
+            fee,
+            fee_collector,
+        } => context.send(&[instruction::change_fee(
+            context.program_id(),
+            &config,
+            &context.keypair.pubkey(),
+            fee_collector.as_ref(),
+            fee,
+        )?]),
+        Command::TransferOwner { config, new_owner } => {
+            context.send(&[instruction::transfer_owner(
+                context.program_id(),
+                &config,
+                &context.keypair.pubkey(),
+                &new_owner,
+            )?])
+        }
+        Command::Bridge(args) => bridge(&context, args),
+        Command::Settle(args) => settle(&context, args),
+        Command::Inspect { address } => {
+            let account =
+                rpc::get_program_account(&context.client, context.program_id(), &address)?;
+            println!("{:#?}", account);
+            Ok(())
+        }
+    }
+}
+
+fn create(context: &Context, args: CreateArgs) -> Result<(), Box<dyn Error>> {
+    let mode = CTokenMode::from(args.mode);
+    let (token_mint, token_program) = match mode {
+        CTokenMode::Native => (NATIVE_MINT, system_program::id()),
+        _ => (
+            args.mint
+                .ok_or("--mint is required for lock and mint cTokens")?,
+            args.token_program,
+        ),
+    };
+    let (c_token, _) = CToken::find_address(
+        context.program_id(),
+        &args.config,
+        &token_mint,
+        args.destination,
+    );
+    let (authority, _) = CToken::find_authority_address(context.program_id(), &c_token);
+    let recipient_format = match args.recipient_format {
+        Some(format) => RecipientFormat::from(format),
+        None => RecipientFormat::for_destination(args.destination)
+            .map_err(|_| "--recipient-format is required for unknown destinations")?,
+    };
+    let token_account = match mode {
+        CTokenMode::Lock => args.vault.ok_or("--vault is required for lock cTokens")?,
+        CTokenMode::Mint => token_mint,
+        CTokenMode::Native => authority,
+    };
+    println!("cToken: {}", c_token);
+    println!("Authority: {}", authority);
+
+    context.send(&[instruction::create(
+        context.program_id(),
+        &args.config,
+        &token_mint,
+        &token_account,
+        &token_program,
+        &context.keypair.pubkey(),
+        &context.keypair.pubkey(),
+        args.destination,
+        args.max,
+        args.min,
+        mode,
+        args.remote_decimals,
+        recipient_format,
+    )?])
+}
+
+fn bridge(context: &Context, args: BridgeArgs) -> Result<(), Box<dyn Error>> {
+    let program_id = context.program_id();
+    let state = rpc::get_c_token(&context.client, program_id, &args.c_token)?;
+    let config = rpc::get_config(&context.client, program_id, &state.config)?;
+    let wallet = context.keypair.pubkey();
+    let destination = args.destination.unwrap_or(state.destination);
+
+    let (route, fee) = if destination == state.destination {
+        (None, config.fee)
+    } else {
+        let (route, _) = Route::find_address(program_id, &args.c_token, destination);
+        let fee = rpc::get_route(&context.client, program_id, &args.c_token, destination)?
+            .ok_or("cToken has no route to the destination")?
+            .fee;
+        (Some(route), fee)
+    };
+    let user_account = match (args.user_account, state.mode) {
+        (Some(user_account), _) => user_account,
+        (None, CTokenMode::Native) => wallet,
+        (None, _) => get_associated_token_address_with_program_id(
+            &wallet,
+            &state.token_mint,
+            &state.token_program_id,
+        ),
+    };
+
+    context.send(&[instruction::bridge(
+        program_id,
+        &args.c_token,
+        &state.token,
+        &user_account,
+        &wallet,
+        &wallet,
+        &state.token_mint,
+        &state.token_program_id,
+        &state.config,
+        route.as_ref(),
+        (fee > 0).then_some((&wallet, &config.fee_collector)),
+        state.has_token_fee().then_some(&state.fee_token),
+        (state.receipt_age > 0).then_some((&wallet, state.index + 1)),
+        &[],
+        destination,
+        args.amount,
+        args.recipient,
+        hex::decode(args.payload.trim_start_matches("0x"))?,
+    )?])
+}
+
+fn settle(context: &Context, args: SettleArgs) -> Result<(), Box<dyn Error>> {
+    let state = rpc::get_c_token(&context.client, context.program_id(), &args.c_token)?;
+    let settle = settle_instruction(
+        &context.client,
+        context.program_id(),
+        &state,
+        &context.keypair.pubkey(),
+        &args,
+    )?;
+    context.send(&[settle])
+}
+
+/// Builds the settle instruction of `args` for the cToken `state` with
+/// `authority` as config authority and payer. A recipient token account is
+/// paid directly, while a wallet recipient of a lock or mint cToken is paid
+/// to its associated token account
+fn settle_instruction(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    state: &CToken,
+    authority: &Pubkey,
+    args: &SettleArgs,
+) -> Result<Instruction, Box<dyn Error>> {
+    let cashier: [u8; CASHIER_ADDRESS_SIZE] = hex::decode(args.cashier.trim_start_matches("0x"))?
+        .try_into()
+        .map_err(|_| "Cashier must be 20 bytes")?;
+    let (c_token_authority, _) = CToken::find_authority_address(program_id, &args.c_token);
+    let transfer_id = TransferId {
+        source_chain: state.destination,
+        cashier,
+        index: args.index,
+    };
+
+    let token_account_owner = match state.mode {
+        CTokenMode::Native => None,
+        _ => token_account_owner(client, state, &args.recipient)?,
+    };
+    let (settle, wallet) = match token_account_owner {
+        Some(owner) => (
+            instruction::settle(
+                program_id,
+                &args.c_token,
+                &c_token_authority,
+                &state.token,
+                &args.recipient,
+                &owner,
+                authority,
+                &state.token_mint,
+                &state.config,
+                &state.token_program_id,
+                authority,
+                args.amount,
+                transfer_id,
+            )?,
+            owner,
+        ),
+        None if state.mode == CTokenMode::Native => (
+            instruction::settle(
+                program_id,
+                &args.c_token,
+                &c_token_authority,
+                &state.token,
+                &args.recipient,
+                &args.recipient,
+                authority,
+                &state.token_mint,
+                &state.config,
+                &state.token_program_id,
+                authority,
+                args.amount,
+                transfer_id,
+            )?,
+            args.recipient,
+        ),
+        None => (
+            instruction::settle_to_wallet(
+                program_id,
+                &args.c_token,
+                &c_token_authority,
+                &state.token,
+                &args.recipient,
+                authority,
+                &state.token_mint,
+                &state.config,
+                &state.token_program_id,
+                authority,
+                args.amount,
+                transfer_id,
+            )?,
+            args.recipient,
+        ),
+    };
+    if state.drop_off > 0 {
+        Ok(instruction::with_drop_off(settle, &state.config, &wallet))
+    } else {
+        Ok(settle)
+    }
+}
+
+/// Owner of `recipient` if it is a token account of the cToken mint, `None`
+/// if it is a wallet or doesn't exist yet
+fn token_account_owner(
+    client: &RpcClient,
+    state: &CToken,
+    recipient: &Pubkey,
+) -> Result<Option<Pubkey>, Box<dyn Error>> {
+    let account = match client
+        .get_account_with_commitment(recipient, client.commitment())?
+        .value
+    {
+        Some(account) if account.owner == system_program::id() => return Ok(None),
+        Some(account) if account.owner == state.token_program_id => account,
+        Some(_) => return Err("Recipient must be a wallet or a token account".into()),
+        None => return Ok(None),
+    };
+    let token_account = StateWithExtensions::<TokenAccount>::unpack(&account.data)?.base;
+    if token_account.mint != state.token_mint {
+        return Err("Recipient token account is not of the cToken mint".into());
+    }
+    Ok(Some(token_account.owner))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use borsh::BorshDeserialize;
+    use ctoken::state::BlockedAccount;
+    use solana_sdk::{native_token::LAMPORTS_PER_SOL, program_pack::Pack, system_instruction};
+    use spl_token_2022::{instruction as token_instruction, state::Mint};
+
+    /// Sends `instructions` paid by `payer` and signed by `signers`
+    fn send(
+        client: &RpcClient,
+        payer: &Keypair,
+        signers: &[&Keypair],
+        instructions: &[Instruction],
+    ) {
+        let mut transaction = Transaction::new_with_payer(instructions, Some(&payer.pubkey()));
+        let mut keypairs = vec![payer];
+        keypairs.extend_from_slice(signers);
+        transaction.sign(&keypairs, client.get_latest_blockhash().unwrap());
+        client.send_and_confirm_transaction(&transaction).unwrap();
+    }
+
+    /// Creates a mint and a token account of it owned by `owner`
+    fn create_token_account(
+        client: &RpcClient,
+        payer: &Keypair,
+        owner: &Pubkey,
+    ) -> (Pubkey, Pubkey) {
+        let mint = Keypair::new();
+        let account = Keypair::new();
+        let mint_rent = client
+            .get_minimum_balance_for_rent_exemption(Mint::LEN)
+            .unwrap();
+        let account_rent = client
+            .get_minimum_balance_for_rent_exemption(TokenAccount::LEN)
+            .unwrap();
+        send(
+            client,
+            payer,
+            &[&mint, &account],
+            &[
+                system_instruction::create_account(
+                    &payer.pubkey(),
+                    &mint.pubkey(),
+                    mint_rent,
+                    Mint::LEN as u64,
+                    &TOKEN_PROGRAM_ID,
+                ),
+                token_instruction::initialize_mint2(
+                    &TOKEN_PROGRAM_ID,
+                    &mint.pubkey(),
+                    &payer.pubkey(),
+                    None,
+                    6,
+                )
+                .unwrap(),
+                system_instruction::create_account(
+                    &payer.pubkey(),
+                    &account.pubkey(),
+                    account_rent,
+                    TokenAccount::LEN as u64,
+                    &TOKEN_PROGRAM_ID,
+                ),
+                token_instruction::initialize_account3(
+                    &TOKEN_PROGRAM_ID,
+                    &account.pubkey(),
+                    &mint.pubkey(),
+                    owner,
+                )
+                .unwrap(),
+            ],
+        );
+        (mint.pubkey(), account.pubkey())
+    }
+
+    /// Needs a local test validator, e.g. `solana-test-validator --reset`,
+    /// at `SOLANA_RPC_URL` or http://localhost:8899
+    #[test]
+    #[ignore]
+    fn test_settle_recipient() {
+        let client = RpcClient::new_with_commitment(
+            std::env::var("SOLANA_RPC_URL").unwrap_or_else(|_| "http://localhost:8899".to_string()),
+            CommitmentConfig::confirmed(),
+        );
+        let payer = Keypair::new();
+        let signature = client
+            .request_airdrop(&payer.pubkey(), LAMPORTS_PER_SOL)
+            .unwrap();
+        while !client.confirm_transaction(&signature).unwrap() {
+            std::thread::sleep(std::time::Duration::from_millis(200));
+        }
+
+        let owner = Pubkey::new_unique();
+        let (token_mint, recipient) = create_token_account(&client, &payer, &owner);
+        let (_, other_recipient) = create_token_account(&client, &payer, &owner);
+
+        let program_id = Pubkey::new_unique();
+        let mut state = CToken::deserialize(&mut &[0u8; CToken::LEN][..]).unwrap();
+        state.token_program_id = TOKEN_PROGRAM_ID;
+        state.config = Pubkey::new_unique();
+        state.token = Pubkey::new_unique();
+        state.token_mint = token_mint;
+        state.destination = 4689;
+        state.mode = CTokenMode::Lock;
+        let args = |recipient| SettleArgs {
+            c_token: Pubkey::new_unique(),
+            recipient,
+            amount: 100,
+            cashier: hex::encode([1u8; CASHIER_ADDRESS_SIZE]),
+            index: 0,
+        };
+
+        // the blocklist is checked against the owner read from the token account
+        let settle = settle_instruction(
+            &client,
+            &program_id,
+            &state,
+            &payer.pubkey(),
+            &args(recipient),
+        )
+        .unwrap();
+        let (blocked, _) = BlockedAccount::find_address(&program_id, &state.config, &owner);
+        assert_eq!(settle.accounts.len(), 12);
+        assert_eq!(settle.accounts[3].pubkey, recipient);
+        assert_eq!(settle.accounts[11].pubkey, blocked);
+
+        // wallets are paid to their associated token account, funded or not
+        for wallet in [payer.pubkey(), Pubkey::new_unique()] {
+            let settle =
+                settle_instruction(&client, &program_id, &state, &payer.pubkey(), &args(wallet))
+                    .unwrap();
+            let (blocked, _) = BlockedAccount::find_address(&program_id, &state.config, &wallet);
+            assert_eq!(settle.accounts.len(), 15);
+            assert_eq!(settle.accounts[3].pubkey, wallet);
+            assert_eq!(settle.accounts[11].pubkey, blocked);
+            assert_eq!(
+                settle.accounts[12].pubkey,
+                get_associated_token_address_with_program_id(
+                    &wallet,
+                    &token_mint,
+                    &TOKEN_PROGRAM_ID
+                )
+            );
+        }
+
+        // token accounts of other mints are rejected
+        assert!(settle_instruction(
+            &client,
+            &program_id,
+            &state,
+            &payer.pubkey(),
+            &args(other_recipient)
+        )
+        .is_err());
+    }
+}
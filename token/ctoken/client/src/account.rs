@@ -0,0 +1,47 @@
+use borsh::BorshDeserialize;
+use ctoken::{
+    error::CTokenError,
+    state::{
+        AccountType, BlockedAccount, BridgeReceipt, CToken, Config, LiquidityProvider, Route,
+        SettlementReceipt,
+    },
+};
+use solana_program::program_error::ProgramError;
+
+/// Account of the cToken program
+#[derive(Clone, Debug)]
+pub enum ProgramAccount {
+    Config(Config),
+    CToken(CToken),
+    SettlementReceipt(SettlementReceipt),
+    LiquidityProvider(LiquidityProvider),
+    Route(Route),
+    BlockedAccount(BlockedAccount),
+    BridgeReceipt(BridgeReceipt),
+}
+
+impl ProgramAccount {
+    /// Decodes account data by its account type. Legacy and outdated config
+    /// and cToken accounts fail with `AccountNotMigrated`
+    pub fn decode(data: &[u8]) -> Result<Self, ProgramError> {
+        let account_type = data
+            .first()
+            .and_then(|t| AccountType::try_from_slice(&[*t]).ok())
+            .ok_or(CTokenError::InvalidAccountType)?;
+        match account_type {
+            AccountType::Config => Ok(Self::Config(Config::unpack(data)?)),
+            AccountType::CToken => Ok(Self::CToken(CToken::unpack(data)?)),
+            AccountType::SettlementReceipt => {
+                Ok(Self::SettlementReceipt(SettlementReceipt::unpack(data)?))
+            }
+            AccountType::LiquidityProvider => {
+                Ok(Self::LiquidityProvider(LiquidityProvider::unpack(data)?))
+            }
+            AccountType::Route => Ok(Self::Route(Route::unpack(data)?)),
+            AccountType::BlockedAccount => Ok(Self::BlockedAccount(BlockedAccount::unpack(data)?)),
+            AccountType::BridgeReceipt => Ok(Self::BridgeReceipt(BridgeReceipt::unpack(data)?)),
+            AccountType::Legacy => Err(CTokenError::AccountNotMigrated.into()),
+            AccountType::Uninitialized => Err(CTokenError::InvalidAccountType.into()),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use solana_rpc_client_api::client_error::Error as ClientError;
+use thiserror::Error;
+
+/// Errors that may be returned by the cToken client.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// RPC request failed
+    #[error("RPC request failed: {0}")]
+    Rpc(Box<ClientError>),
+    /// Account does not exist
+    #[error("Account {0} not found")]
+    AccountNotFound(Pubkey),
+    /// Account is not owned by the cToken program
+    #[error("Account {0} is not owned by the cToken program")]
+    InvalidOwner(Pubkey),
+    /// Account data can't be decoded
+    #[error("Invalid account data: {0}")]
+    InvalidAccountData(#[from] ProgramError),
+    /// Logs are truncated by the runtime, so events may be missing
+    #[error("Logs are truncated")]
+    LogTruncated,
+    /// Transaction metadata has no logs
+    #[error("Transaction metadata has no logs")]
+    MissingLogs,
+    /// Log line doesn't follow the runtime invocation logs
+    #[error("Unexpected log at line {0}")]
+    InvalidLogs(usize),
+    /// Event log of the cToken program can't be decoded
+    #[error("Invalid event at line {0}")]
+    InvalidEvent(usize),
+    /// Event of a cToken program invocation at an unexpected depth
+    #[error("Event at invocation depth {depth}, expected {expected}")]
+    UnexpectedDepth { depth: usize, expected: usize },
+}
+
+impl From<ClientError> for Error {
+    fn from(e: ClientError) -> Self {
+        Error::Rpc(Box::new(e))
+    }
+}
@@ -0,0 +1,9 @@
+//! Client for the cToken program: instruction builders, account decoding,
+//! account fetching over RPC and event decoding from transaction logs
+
+pub mod account;
+pub mod error;
+pub mod logs;
+pub mod rpc;
+
+pub use ctoken::{instruction, log, state};
@@ -0,0 +1,191 @@
+//! Decodes cToken program events from transaction logs.
+//!
+//! The runtime writes the invoke, success and failed lines itself, while
+//! program output is always prefixed with `Program log: ` or `Program data: `.
+//! Following those lines gives the program that wrote each log, so an event
+//! is only taken from invocations of the cToken program and a `msg!` of
+//! another program copying it is ignored.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use borsh::BorshDeserialize;
+use ctoken::log::{Bridge, Event};
+use solana_program::pubkey::Pubkey;
+use solana_transaction_status::{option_serializer::OptionSerializer, UiTransactionStatusMeta};
+
+use crate::error::Error;
+
+/// Event of a cToken program invocation
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProgramEvent {
+    /// Index of the top level instruction
+    pub instruction: usize,
+
+    /// Invocation depth of the cToken program, 1 for top level instructions
+    pub depth: usize,
+
+    /// Event
+    pub event: Event,
+}
+
+struct Invocation {
+    program_id: String,
+    events: Vec<Event>,
+    legacy_bridges: Vec<Bridge>,
+}
+
+/// Decodes the events of transaction logs. Only invocations of `program_id`
+/// are decoded, and with an expected `depth` an event of an invocation at
+/// another depth fails with `UnexpectedDepth`. Logs of a failed transaction
+/// have no events.
+///
+/// `Bridge: <hex>` logs of invocations without a Bridge event, logged by
+/// program versions before events, are decoded as Bridge events.
+pub fn decode_logs(
+    program_id: &Pubkey,
+    depth: Option<usize>,
+    logs: &[String],
+) -> Result<Vec<ProgramEvent>, Error> {
+    let program_id = program_id.to_string();
+    let mut stack: Vec<Invocation> = vec![];
+    let mut instructions = 0;
+    let mut instruction = 0;
+    let mut events = vec![];
+
+    for (line, log) in logs.iter().enumerate() {
+        if let Some(message) = log.strip_prefix("Program log: ") {
+            let invocation = stack.last_mut().ok_or(Error::InvalidLogs(line))?;
+            if invocation.program_id != program_id {
+                continue;
+            }
+            if let Some(data) = message.strip_prefix("Bridge: ") {
+                let bridge = hex::decode(data)
+                    .ok()
+                    .and_then(|data| decode_legacy_bridge(&data).ok())
+                    .ok_or(Error::InvalidEvent(line))?;
+                invocation.legacy_bridges.push(bridge);
+            }
+        } else if let Some(data) = log.strip_prefix("Program data: ") {
+            let invocation = stack.last_mut().ok_or(Error::InvalidLogs(line))?;
+            if invocation.program_id != program_id {
+                continue;
+            }
+            let event = STANDARD
+                .decode(data)
+                .ok()
+                .and_then(|data| Event::decode(&data).ok())
+                .ok_or(Error::InvalidEvent(line))?;
+            invocation.events.push(event);
+        } else if log.starts_with("Program return: ") {
+            continue;
+        } else if log == "Log truncated" {
+            return Err(Error::LogTruncated);
+        } else if let Some((id, status)) = log
+            .strip_prefix("Program ")
+            .and_then(|log| log.split_once(' '))
+        {
+            if let Some(invoke_depth) = status
+                .strip_prefix("invoke [")
+                .and_then(|status| status.strip_suffix(']'))
+            {
+                if invoke_depth.parse() != Ok(stack.len() + 1) {
+                    return Err(Error::InvalidLogs(line));
+                }
+                if stack.is_empty() {
+                    instruction = instructions;
+                    instructions += 1;
+                }
+                stack.push(Invocation {
+                    program_id: id.to_string(),
+                    events: vec![],
+                    legacy_bridges: vec![],
+                });
+            } else if status == "success" {
+                let invocation = stack.pop().ok_or(Error::InvalidLogs(line))?;
+                if invocation.program_id != id {
+                    return Err(Error::InvalidLogs(line));
+                }
+                let mut invocation_events = invocation.events;
+                if !invocation_events
+                    .iter()
+                    .any(|event| matches!(event, Event::Bridge(_)))
+                {
+                    invocation_events
+                        .extend(invocation.legacy_bridges.into_iter().map(Event::Bridge));
+                }
+                let invocation_depth = stack.len() + 1;
+                match depth {
+                    Some(expected)
+                        if expected != invocation_depth && !invocation_events.is_empty() =>
+                    {
+                        return Err(Error::UnexpectedDepth {
+                            depth: invocation_depth,
+                            expected,
+                        });
+                    }
+                    _ => {}
+                }
+                events.extend(invocation_events.into_iter().map(|event| ProgramEvent {
+                    instruction,
+                    depth: invocation_depth,
+                    event,
+                }));
+            } else if status.starts_with("failed") {
+                return Ok(vec![]);
+            }
+        }
+    }
+
+    if !stack.is_empty() {
+        return Err(Error::InvalidLogs(logs.len()));
+    }
+    Ok(events)
+}
+
+/// Decodes the events of a transaction from its metadata, see [`decode_logs`]
+pub fn decode_transaction_meta(
+    program_id: &Pubkey,
+    depth: Option<usize>,
+    meta: &UiTransactionStatusMeta,
+) -> Result<Vec<ProgramEvent>, Error> {
+    if meta.err.is_some() {
+        return Ok(vec![]);
+    }
+    match &meta.log_messages {
+        OptionSerializer::Some(logs) => decode_logs(program_id, depth, logs),
+        _ => Err(Error::MissingLogs),
+    }
+}
+
+/// Decodes a `Bridge: <hex>` log. Logs of earlier program versions lack the
+/// trailing token fee and remote amount, which are decoded as no token fee
+/// and the unscaled amount.
+pub fn decode_legacy_bridge(data: &[u8]) -> std::io::Result<Bridge> {
+    let buf = &mut &data[..];
+    let mut bridge = Bridge {
+        token: Pubkey::deserialize(buf)?,
+        index: u64::deserialize(buf)?,
+        sender: Pubkey::deserialize(buf)?,
+        recipient: String::deserialize(buf)?,
+        amount: u64::deserialize(buf)?,
+        fee: u64::deserialize(buf)?,
+        destination: u32::deserialize(buf)?,
+        payload: Vec::<u8>::deserialize(buf)?,
+        token_fee: 0,
+        remote_amount: 0,
+    };
+    if !buf.is_empty() {
+        bridge.token_fee = u64::deserialize(buf)?;
+    }
+    bridge.remote_amount = if buf.is_empty() {
+        bridge.amount.into()
+    } else {
+        u128::deserialize(buf)?
+    };
+    if !buf.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "unexpected trailing bytes",
+        ));
+    }
+    Ok(bridge)
+}
@@ -0,0 +1,131 @@
+use ctoken::state::{
+    BlockedAccount, BridgeReceipt, CToken, Config, LiquidityProvider, Route, SettlementReceipt,
+    TransferId,
+};
+use solana_program::pubkey::Pubkey;
+use solana_rpc_client::rpc_client::RpcClient;
+
+use crate::{account::ProgramAccount, error::Error};
+
+/// Fetches the data of a cToken program account, `None` if it doesn't exist
+pub fn get_account_data(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    address: &Pubkey,
+) -> Result<Option<Vec<u8>>, Error> {
+    let account = client
+        .get_account_with_commitment(address, client.commitment())?
+        .value;
+    match account {
+        Some(account) if account.owner == *program_id => Ok(Some(account.data)),
+        Some(_) => Err(Error::InvalidOwner(*address)),
+        None => Ok(None),
+    }
+}
+
+fn get_existing_account_data(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    address: &Pubkey,
+) -> Result<Vec<u8>, Error> {
+    get_account_data(client, program_id, address)?.ok_or(Error::AccountNotFound(*address))
+}
+
+/// Fetches and decodes any cToken program account
+pub fn get_program_account(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    address: &Pubkey,
+) -> Result<ProgramAccount, Error> {
+    let data = get_existing_account_data(client, program_id, address)?;
+    Ok(ProgramAccount::decode(&data)?)
+}
+
+/// Fetches and decodes the config at `address`
+pub fn get_config(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    address: &Pubkey,
+) -> Result<Config, Error> {
+    let data = get_existing_account_data(client, program_id, address)?;
+    Ok(Config::unpack(&data)?)
+}
+
+/// Fetches and decodes the cToken at `address`
+pub fn get_c_token(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    address: &Pubkey,
+) -> Result<CToken, Error> {
+    let data = get_existing_account_data(client, program_id, address)?;
+    Ok(CToken::unpack(&data)?)
+}
+
+/// Fetches the settlement receipt of `transfer_id`, `None` while unsettled
+pub fn get_settlement_receipt(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    config: &Pubkey,
+    transfer_id: &TransferId,
+) -> Result<Option<SettlementReceipt>, Error> {
+    let (address, _) = SettlementReceipt::find_address(program_id, config, transfer_id);
+    get_account_data(client, program_id, &address)?
+        .map(|data| SettlementReceipt::unpack(&data))
+        .transpose()
+        .map_err(Error::from)
+}
+
+/// Fetches the receipt of bridge `index` of `c_token`, `None` if the cToken
+/// wrote no receipt or it is closed
+pub fn get_bridge_receipt(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    c_token: &Pubkey,
+    index: u64,
+) -> Result<Option<BridgeReceipt>, Error> {
+    let (address, _) = BridgeReceipt::find_address(program_id, c_token, index);
+    get_account_data(client, program_id, &address)?
+        .map(|data| BridgeReceipt::unpack(&data))
+        .transpose()
+        .map_err(Error::from)
+}
+
+/// Fetches the liquidity provider `provider` of `c_token`, `None` if never
+/// whitelisted
+pub fn get_liquidity_provider(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    c_token: &Pubkey,
+    provider: &Pubkey,
+) -> Result<Option<LiquidityProvider>, Error> {
+    let (address, _) = LiquidityProvider::find_address(program_id, c_token, provider);
+    get_account_data(client, program_id, &address)?
+        .map(|data| LiquidityProvider::unpack(&data))
+        .transpose()
+        .map_err(Error::from)
+}
+
+/// Fetches the route of `c_token` to `destination`, `None` if not added
+pub fn get_route(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    c_token: &Pubkey,
+    destination: u32,
+) -> Result<Option<Route>, Error> {
+    let (address, _) = Route::find_address(program_id, c_token, destination);
+    get_account_data(client, program_id, &address)?
+        .map(|data| Route::unpack(&data))
+        .transpose()
+        .map_err(Error::from)
+}
+
+/// Whether `account` is on the blocklist of `config`
+pub fn is_blocked(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    config: &Pubkey,
+    account: &Pubkey,
+) -> Result<bool, Error> {
+    let (address, _) = BlockedAccount::find_address(program_id, config, account);
+    Ok(get_account_data(client, program_id, &address)?.is_some())
+}
@@ -0,0 +1,307 @@
+use ctoken::{
+    log::{Bridge, Event, PauseChanged, Settle},
+    state::TransferId,
+};
+use ctoken_client::{
+    error::Error,
+    logs::{decode_legacy_bridge, decode_logs, decode_transaction_meta, ProgramEvent},
+};
+use solana_program::{pubkey, pubkey::Pubkey};
+use solana_transaction_status::UiTransactionStatusMeta;
+
+const PROGRAM_ID: Pubkey = pubkey!("2Ranq8nkdU7kqgqjzkpaXVQ9SSdvYu5zoteMVVoBBiBZ");
+const OTHER_PROGRAM_ID: &str = "Ex9CSwfvbuobTGLqYtvG5Wvvm1tuvpbJJYgMpVSHJF7t";
+
+/// Bridge event of the current program
+const BRIDGE_HEX: &str = "4339dca051dd01e2df7bf80a00dbf2a76399d3716ba2a17d81d6a89c1442d4b70700000000000000976400bafc9f2ac78f2fdd5f154cb381cf6f1321afd7782a6e1280b522442e802a0000003078623731303434623663643834336464633131653432393865396461363464613030383634653039398033023b000000008813000000000000511200000000000080969800000000000000a337c12fbd0d0000000000000000";
+const BRIDGE_DATA: &str = "AQBDOdygUd0B4t97+AoA2/KnY5nTcWuioX2B1qicFELUtwcAAAAAAAAAl2QAuvyfKsePL91fFUyzgc9vEyGv13gqbhKAtSJELoAqAAAAMHhiNzEwNDRiNmNkODQzZGRjMTFlNDI5OGU5ZGE2NGRhMDA4NjRlMDk5gDMCOwAAAACIEwAAAAAAAFESAAAAAAAAgJaYAAAAAAAAAKM3wS+9DQAAAAAAAAAA";
+
+/// Bridge log of the program before token fees and events
+const LEGACY_BRIDGE_HEX: &str = "bfe83295da43156698c425b75599ff3c6a637ed4d49544659fc92efaa5c47ba202000000000000002aac73de831e428cc021c172a6de30f16a22115f5beeb67c41023a4d968e9a8a2a00000030786237313034346236636438343364646331316534323938653964613634646130303836346530393940420f0000000000000000000000000052120000020000006768";
+
+const SETTLE_DATA: &str = "AQFDOdygUd0B4t97+AoA2/KnY5nTcWuioX2B1qicFELUt2kv4oW25rjd4hqhE6C2rmGkLTUxOfZ0wxeQb9/CV/kIURIAALcQRLbNhD3cEeQpjp2mTaAIZOCZKgAAAAAAAADf1h8wjtgiOwHalHGTCQN7Q+S+ehkuMVMkQzNukc6QwQDKmjsAAAAA";
+
+const PAUSE_DATA: &str = "AQfO5RRht4zZrGaixtLj/py4pHBPpzQ+cJ8S3rUX0G8F7wE=";
+
+fn bridge() -> Bridge {
+    Bridge {
+        token: pubkey!("5XRVN8CPXWiuii9ufuEE5LmZSdtg9qvKFHpvJCL5UTsc"),
+        index: 7,
+        sender: pubkey!("BBy1K96Y3bohNeiZTHuQyB53LcfZv6NWCSWqQp89TiVu"),
+        recipient: "0xb71044b6cd843ddc11e4298e9da64da00864e099".to_string(),
+        amount: 990000000,
+        fee: 5000,
+        destination: 4689,
+        payload: vec![],
+        token_fee: 10000000,
+        remote_amount: 990000000000000000,
+    }
+}
+
+fn settle() -> Settle {
+    let mut cashier = [0; 20];
+    cashier.copy_from_slice(&hex::decode("b71044b6cd843ddc11e4298e9da64da00864e099").unwrap());
+    Settle {
+        token: pubkey!("5XRVN8CPXWiuii9ufuEE5LmZSdtg9qvKFHpvJCL5UTsc"),
+        c_token: pubkey!("85cAA5dLWmD4NXwXKXKdj7cWXZHw7kNtJLJ6XHxgW5hH"),
+        transfer_id: TransferId {
+            source_chain: 4689,
+            cashier,
+            index: 42,
+        },
+        recipient: pubkey!("G4mHebL2E6vRespbLtSZC4BAofQhLpyocB5kthadNJb2"),
+        amount: 1000000000,
+    }
+}
+
+fn logs(lines: &[&str]) -> Vec<String> {
+    lines
+        .iter()
+        .map(|line| {
+            line.replace("{program}", &PROGRAM_ID.to_string())
+                .replace("{other}", OTHER_PROGRAM_ID)
+                .replace("{bridge_hex}", BRIDGE_HEX)
+                .replace("{bridge_data}", BRIDGE_DATA)
+                .replace("{legacy_bridge_hex}", LEGACY_BRIDGE_HEX)
+                .replace("{settle_data}", SETTLE_DATA)
+                .replace("{pause_data}", PAUSE_DATA)
+        })
+        .collect()
+}
+
+#[test]
+fn test_bridge() {
+    let logs = logs(&[
+        "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+        "Program ComputeBudget111111111111111111111111111111 success",
+        "Program {program} invoke [1]",
+        "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [2]",
+        "Program log: Instruction: TransferChecked",
+        "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA consumed 6200 of 180000 compute units",
+        "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA success",
+        "Program log: Bridge: {bridge_hex}",
+        "Program data: {bridge_data}",
+        "Program {program} consumed 40000 of 200000 compute units",
+        "Program {program} success",
+    ]);
+    let expected = vec![ProgramEvent {
+        instruction: 1,
+        depth: 1,
+        event: Event::Bridge(bridge()),
+    }];
+    assert_eq!(decode_logs(&PROGRAM_ID, Some(1), &logs).unwrap(), expected);
+    assert_eq!(decode_logs(&PROGRAM_ID, None, &logs).unwrap(), expected);
+}
+
+#[test]
+fn test_legacy_bridge() {
+    let bridge = decode_legacy_bridge(&hex::decode(LEGACY_BRIDGE_HEX).unwrap()).unwrap();
+    assert_eq!(
+        bridge.token,
+        pubkey!("Dv8EAecmZcAGBpunFjoYhmuvek8wrKN4g7rYuKh3Ha4q")
+    );
+    assert_eq!(bridge.index, 2);
+    assert_eq!(
+        bridge.recipient,
+        "0xb71044b6cd843ddc11e4298e9da64da00864e099"
+    );
+    assert_eq!(bridge.amount, 1000000);
+    assert_eq!(bridge.fee, 0);
+    assert_eq!(bridge.destination, 4690);
+    assert_eq!(bridge.payload, b"gh");
+    assert_eq!(bridge.token_fee, 0);
+    assert_eq!(bridge.remote_amount, 1000000);
+
+    assert_eq!(
+        decode_legacy_bridge(&hex::decode(BRIDGE_HEX).unwrap()).unwrap(),
+        self::bridge()
+    );
+
+    let logs = logs(&[
+        "Program {program} invoke [1]",
+        "Program log: Bridge: {legacy_bridge_hex}",
+        "Program {program} success",
+    ]);
+    assert_eq!(
+        decode_logs(&PROGRAM_ID, Some(1), &logs).unwrap(),
+        vec![ProgramEvent {
+            instruction: 0,
+            depth: 1,
+            event: Event::Bridge(bridge),
+        }]
+    );
+}
+
+#[test]
+fn test_spoofed_logs() {
+    let logs = logs(&[
+        "Program {other} invoke [1]",
+        "Program log: Bridge: {bridge_hex}",
+        "Program data: {bridge_data}",
+        "Program log: Program {program} invoke [2]",
+        "Program log: Program data: {bridge_data}",
+        "Program log: Program {program} success",
+        "Program {other} success",
+        "Program {program} invoke [1]",
+        "Program log: Program {other} invoke [2]",
+        "Program data: {pause_data}",
+        "Program log: Program {other} success",
+        "Program {program} success",
+    ]);
+    assert_eq!(
+        decode_logs(&PROGRAM_ID, Some(1), &logs).unwrap(),
+        vec![ProgramEvent {
+            instruction: 1,
+            depth: 1,
+            event: Event::PauseChanged(PauseChanged {
+                account: pubkey!("EvdaveTPSkJ3UReURhijs74MmpzmFWNnbEdtPvsddoqx"),
+                paused: true,
+            }),
+        }]
+    );
+}
+
+#[test]
+fn test_invocation_depth() {
+    let logs = logs(&[
+        "Program {other} invoke [1]",
+        "Program {program} invoke [2]",
+        "Program data: {settle_data}",
+        "Program {program} success",
+        "Program {other} success",
+    ]);
+    assert_eq!(
+        decode_logs(&PROGRAM_ID, None, &logs).unwrap(),
+        vec![ProgramEvent {
+            instruction: 0,
+            depth: 2,
+            event: Event::Settle(settle()),
+        }]
+    );
+    assert!(matches!(
+        decode_logs(&PROGRAM_ID, Some(1), &logs),
+        Err(Error::UnexpectedDepth {
+            depth: 2,
+            expected: 1
+        })
+    ));
+}
+
+#[test]
+fn test_failed_transaction() {
+    let logs = logs(&[
+        "Program {program} invoke [1]",
+        "Program data: {bridge_data}",
+        "Program {program} success",
+        "Program {program} invoke [1]",
+        "Program log: Error: Amount is invalid",
+        "Program {program} consumed 3000 of 200000 compute units",
+        "Program {program} failed: custom program error: 0x4",
+    ]);
+    assert_eq!(decode_logs(&PROGRAM_ID, Some(1), &logs).unwrap(), vec![]);
+}
+
+#[test]
+fn test_invalid_logs() {
+    let truncated = logs(&[
+        "Program {program} invoke [1]",
+        "Program data: {bridge_data}",
+        "Log truncated",
+    ]);
+    assert!(matches!(
+        decode_logs(&PROGRAM_ID, None, &truncated),
+        Err(Error::LogTruncated)
+    ));
+
+    let outside_invocation = logs(&["Program data: {bridge_data}"]);
+    assert!(matches!(
+        decode_logs(&PROGRAM_ID, None, &outside_invocation),
+        Err(Error::InvalidLogs(0))
+    ));
+
+    let skipped_depth = logs(&[
+        "Program {other} invoke [1]",
+        "Program {program} invoke [3]",
+        "Program {program} success",
+        "Program {other} success",
+    ]);
+    assert!(matches!(
+        decode_logs(&PROGRAM_ID, None, &skipped_depth),
+        Err(Error::InvalidLogs(1))
+    ));
+
+    let unfinished = logs(&[
+        "Program {program} invoke [1]",
+        "Program data: {bridge_data}",
+    ]);
+    assert!(matches!(
+        decode_logs(&PROGRAM_ID, None, &unfinished),
+        Err(Error::InvalidLogs(2))
+    ));
+
+    let invalid_event = logs(&[
+        "Program {program} invoke [1]",
+        "Program data: AgA=",
+        "Program {program} success",
+    ]);
+    assert!(matches!(
+        decode_logs(&PROGRAM_ID, None, &invalid_event),
+        Err(Error::InvalidEvent(1))
+    ));
+}
+
+#[test]
+fn test_transaction_meta() {
+    let meta: UiTransactionStatusMeta = serde_json::from_value(serde_json::json!({
+        "err": null,
+        "status": { "Ok": null },
+        "fee": 5000,
+        "preBalances": [],
+        "postBalances": [],
+        "logMessages": logs(&[
+            "Program {program} invoke [1]",
+            "Program data: {settle_data}",
+            "Program {program} success",
+        ]),
+    }))
+    .unwrap();
+    assert_eq!(
+        decode_transaction_meta(&PROGRAM_ID, Some(1), &meta).unwrap(),
+        vec![ProgramEvent {
+            instruction: 0,
+            depth: 1,
+            event: Event::Settle(settle()),
+        }]
+    );
+
+    let failed: UiTransactionStatusMeta = serde_json::from_value(serde_json::json!({
+        "err": { "InstructionError": [0, { "Custom": 4 }] },
+        "status": { "Err": { "InstructionError": [0, { "Custom": 4 }] } },
+        "fee": 5000,
+        "preBalances": [],
+        "postBalances": [],
+        "logMessages": logs(&[
+            "Program {program} invoke [1]",
+            "Program data: {settle_data}",
+            "Program {program} failed: custom program error: 0x4",
+        ]),
+    }))
+    .unwrap();
+    assert_eq!(
+        decode_transaction_meta(&PROGRAM_ID, Some(1), &failed).unwrap(),
+        vec![]
+    );
+
+    let without_logs: UiTransactionStatusMeta = serde_json::from_value(serde_json::json!({
+        "err": null,
+        "status": { "Ok": null },
+        "fee": 5000,
+        "preBalances": [],
+        "postBalances": [],
+    }))
+    .unwrap();
+    assert!(matches!(
+        decode_transaction_meta(&PROGRAM_ID, Some(1), &without_logs),
+        Err(Error::MissingLogs)
+    ));
+}
@@ -1,4 +1,5 @@
 import type {
+    AccountMeta,
     ConfirmOptions,
     Connection,
     Keypair,
@@ -11,7 +12,11 @@ import {
 } from '@solana/web3.js';
 import {PublicKey, SystemProgram, Transaction} from '@solana/web3.js';
 import * as borsh from 'borsh';
-import {createApproveInstruction} from '@solana/spl-token';
+import {
+    ASSOCIATED_TOKEN_PROGRAM_ID,
+    createApproveInstruction,
+    getAssociatedTokenAddressSync,
+} from '@solana/spl-token';
 
 class Assignable {
     // @ts-ignore
@@ -22,6 +27,56 @@ class Assignable {
         });
     }
 }
+export enum AccountType {
+    Uninitialized = 0,
+    Legacy,
+    Config,
+    CToken,
+    SettlementReceipt,
+    LiquidityProvider,
+    Route,
+    BlockedAccount,
+    BridgeReceipt,
+}
+
+export enum CTokenMode {
+    Lock = 0,
+    Mint,
+    Native,
+}
+
+// recipient address format of a destination, stored on cTokens and routes
+export enum RecipientFormat {
+    Evm = 0,
+    IoTeX,
+}
+
+// default recipient format of a well-known destination
+export function recipientFormatOf(
+    destination: number,
+): RecipientFormat | undefined {
+    switch (destination) {
+        case 0:
+        case 4689:
+        case 4690:
+            return RecipientFormat.IoTeX;
+        case 1:
+        case 56:
+        case 97:
+        case 137:
+        case 80002:
+        case 11155111:
+            return RecipientFormat.Evm;
+        default:
+            return undefined;
+    }
+}
+
+// token mint of native cTokens, which lock SOL in the cToken authority
+export const NATIVE_MINT = new PublicKey(
+    'So11111111111111111111111111111111111111112',
+);
+
 export class cTokenConfig extends Assignable {}
 export const cTokenConfigSchema = new Map([
     [
@@ -29,23 +84,41 @@ export const cTokenConfigSchema = new Map([
         {
             kind: 'struct',
             fields: [
-                ['initialized', 'u8'],
+                ['account_type', 'u8'],
+                ['version', 'u8'],
                 ['owner', [32]],
                 ['authority', [32]],
                 ['fee', 'u64'],
                 ['fee_collector', [32]],
+                ['paused', 'u8'],
+                ['pending_owner', {kind: 'option', type: [32]}],
+                ['pending_authority', {kind: 'option', type: [32]}],
             ],
         },
     ],
 ]);
+export class VolumeLimit extends Assignable {}
 export class cTokenAccount extends Assignable {}
-export const cTokenAccountSchema = new Map([
+export const cTokenAccountSchema = new Map<any, any>([
+    [
+        VolumeLimit,
+        {
+            kind: 'struct',
+            fields: [
+                ['cap', 'u64'],
+                ['volume', 'u64'],
+                ['previous_volume', 'u64'],
+                ['window_start', 'u64'],
+            ],
+        },
+    ],
     [
         cTokenAccount,
         {
             kind: 'struct',
             fields: [
-                ['initialized', 'u8'],
+                ['account_type', 'u8'],
+                ['version', 'u8'],
                 ['bump_seed', 'u8'],
                 ['token_program_id', [32]],
                 ['config', [32]],
@@ -55,6 +128,41 @@ export const cTokenAccountSchema = new Map([
                 ['index', 'u64'],
                 ['max', 'u64'],
                 ['min', 'u64'],
+                ['paused', 'u8'],
+                ['window', 'u64'],
+                ['outbound', VolumeLimit],
+                ['inbound', VolumeLimit],
+                ['fee_bps', 'u16'],
+                ['min_fee', 'u64'],
+                ['max_fee', 'u64'],
+                ['fee_token', [32]],
+                ['liquidity', 'u64'],
+                ['liquidity_reserve', 'u64'],
+                ['mode', 'u8'],
+                ['decimals', 'u8'],
+                ['remote_decimals', 'u8'],
+                ['receipt_age', 'u64'],
+                ['drop_off', 'u64'],
+                ['drop_off_threshold', 'u64'],
+                ['open_receipts', 'u64'],
+                ['recipient_format', 'u8'],
+            ],
+        },
+    ],
+]);
+
+export class LiquidityProviderAccount extends Assignable {}
+export const LiquidityProviderAccountSchema = new Map([
+    [
+        LiquidityProviderAccount,
+        {
+            kind: 'struct',
+            fields: [
+                ['account_type', 'u8'],
+                ['c_token', [32]],
+                ['provider', [32]],
+                ['enabled', 'u8'],
+                ['amount', 'u64'],
             ],
         },
     ],
@@ -75,6 +183,8 @@ export const BridgeLogSchema = new Map([
                 ['fee', 'u64'],
                 ['destination', 'u32'],
                 ['payload', ['u8']],
+                ['token_fee', 'u64'],
+                ['remote_amount', 'u128'],
             ],
         },
     ],
@@ -83,7 +193,8 @@ export const BridgeLogSchema = new Map([
 export const C_TOKEN_CONFIG_SIZE = borsh.serialize(
     cTokenConfigSchema,
     new cTokenConfig({
-        initialized: true,
+        account_type: AccountType.Config,
+        version: 1,
         owner: new PublicKey(
             '7vLEZP5JHhKVg3HEGSWcFNaxAKg7L633uMT7ePqmn98V',
         ).toBytes(),
@@ -94,12 +205,21 @@ export const C_TOKEN_CONFIG_SIZE = borsh.serialize(
         fee_collector: new PublicKey(
             '7vLEZP5JHhKVg3HEGSWcFNaxAKg7L633uMT7ePqmn98V',
         ).toBytes(),
+        paused: false,
+        // allocate space for pending changes
+        pending_owner: new PublicKey(
+            '7vLEZP5JHhKVg3HEGSWcFNaxAKg7L633uMT7ePqmn98V',
+        ).toBytes(),
+        pending_authority: new PublicKey(
+            '7vLEZP5JHhKVg3HEGSWcFNaxAKg7L633uMT7ePqmn98V',
+        ).toBytes(),
     }),
 ).length;
 export const C_TOKEN_ACCOUNT_SIZE = borsh.serialize(
     cTokenAccountSchema,
     new cTokenAccount({
-        initialized: true,
+        account_type: AccountType.CToken,
+        version: 3,
         bump_seed: 100,
         token_program_id: new PublicKey(
             '7vLEZP5JHhKVg3HEGSWcFNaxAKg7L633uMT7ePqmn98V',
@@ -117,6 +237,36 @@ export const C_TOKEN_ACCOUNT_SIZE = borsh.serialize(
         index: 0,
         max: 1000000000000,
         min: 100000000,
+        paused: false,
+        window: 0,
+        outbound: new VolumeLimit({
+            cap: 0,
+            volume: 0,
+            previous_volume: 0,
+            window_start: 0,
+        }),
+        inbound: new VolumeLimit({
+            cap: 0,
+            volume: 0,
+            previous_volume: 0,
+            window_start: 0,
+        }),
+        fee_bps: 0,
+        min_fee: 0,
+        max_fee: 0,
+        fee_token: new PublicKey(
+            '7vLEZP5JHhKVg3HEGSWcFNaxAKg7L633uMT7ePqmn98V',
+        ).toBytes(),
+        liquidity: 0,
+        liquidity_reserve: 0,
+        mode: CTokenMode.Lock,
+        decimals: 9,
+        remote_decimals: 18,
+        receipt_age: 0,
+        drop_off: 0,
+        drop_off_threshold: 0,
+        open_receipts: 0,
+        recipient_format: RecipientFormat.IoTeX,
     }),
 ).length;
 
@@ -129,6 +279,34 @@ enum InstructionVariant {
     ChangeLimit,
     Bridge,
     Settle,
+    Pause,
+    Unpause,
+    ChangeVolumeLimit,
+    ChangeTokenFee,
+    WithdrawTokenFee,
+    AcceptOwner,
+    AcceptAuthority,
+    CancelPendingOwner,
+    CancelPendingAuthority,
+    Migrate,
+    CloseCToken,
+    SetLiquidityProvider,
+    AddLiquidity,
+    RemoveLiquidity,
+    ChangeLiquidityReserve,
+    AddRoute,
+    RemoveRoute,
+    ChangeDecimals,
+    Block,
+    Unblock,
+    SettleAndCall,
+    ChangeReceiptAge,
+    CloseBridgeReceipt,
+    SettleBatch,
+    WithdrawRentVault,
+    ChangeDropOff,
+    FundDropOffVault,
+    WithdrawDropOffVault,
 }
 
 class ConfigPayload extends Assignable {}
@@ -140,6 +318,7 @@ const ConfigPayloadSchema = new Map([
             fields: [
                 ['id', 'u8'],
                 ['fee', 'u64'],
+                ['seed', 'string'],
             ],
         },
     ],
@@ -167,6 +346,214 @@ const ChangeAuthorityPayloadSchema = new Map([
     ],
 ]);
 
+class PausePayload extends Assignable {}
+const PausePayloadSchema = new Map([
+    [
+        PausePayload,
+        {
+            kind: 'struct',
+            fields: [['id', 'u8']],
+        },
+    ],
+]);
+
+class AcceptPayload extends Assignable {}
+const AcceptPayloadSchema = new Map([
+    [
+        AcceptPayload,
+        {
+            kind: 'struct',
+            fields: [['id', 'u8']],
+        },
+    ],
+]);
+
+class CancelPendingPayload extends Assignable {}
+const CancelPendingPayloadSchema = new Map([
+    [
+        CancelPendingPayload,
+        {
+            kind: 'struct',
+            fields: [['id', 'u8']],
+        },
+    ],
+]);
+
+class MigratePayload extends Assignable {}
+const MigratePayloadSchema = new Map([
+    [
+        MigratePayload,
+        {
+            kind: 'struct',
+            fields: [
+                ['id', 'u8'],
+                ['remote_decimals', 'u8'],
+            ],
+        },
+    ],
+]);
+
+class CloseCTokenPayload extends Assignable {}
+const CloseCTokenPayloadSchema = new Map([
+    [
+        CloseCTokenPayload,
+        {
+            kind: 'struct',
+            fields: [['id', 'u8']],
+        },
+    ],
+]);
+
+class SetLiquidityProviderPayload extends Assignable {}
+const SetLiquidityProviderPayloadSchema = new Map([
+    [
+        SetLiquidityProviderPayload,
+        {
+            kind: 'struct',
+            fields: [
+                ['id', 'u8'],
+                ['enabled', 'u8'],
+            ],
+        },
+    ],
+]);
+
+class LiquidityPayload extends Assignable {}
+const LiquidityPayloadSchema = new Map([
+    [
+        LiquidityPayload,
+        {
+            kind: 'struct',
+            fields: [
+                ['id', 'u8'],
+                ['amount', 'u64'],
+            ],
+        },
+    ],
+]);
+
+class ChangeLiquidityReservePayload extends Assignable {}
+const ChangeLiquidityReservePayloadSchema = new Map([
+    [
+        ChangeLiquidityReservePayload,
+        {
+            kind: 'struct',
+            fields: [
+                ['id', 'u8'],
+                ['reserve', 'u64'],
+            ],
+        },
+    ],
+]);
+
+class AddRoutePayload extends Assignable {}
+const AddRoutePayloadSchema = new Map([
+    [
+        AddRoutePayload,
+        {
+            kind: 'struct',
+            fields: [
+                ['id', 'u8'],
+                ['destination', 'u32'],
+                ['max', 'u64'],
+                ['min', 'u64'],
+                ['fee', 'u64'],
+                ['recipient_format', 'u8'],
+            ],
+        },
+    ],
+]);
+
+class RemoveRoutePayload extends Assignable {}
+const RemoveRoutePayloadSchema = new Map([
+    [
+        RemoveRoutePayload,
+        {
+            kind: 'struct',
+            fields: [['id', 'u8']],
+        },
+    ],
+]);
+
+class BlockPayload extends Assignable {}
+const BlockPayloadSchema = new Map([
+    [
+        BlockPayload,
+        {
+            kind: 'struct',
+            fields: [
+                ['id', 'u8'],
+                ['account', [32]],
+            ],
+        },
+    ],
+]);
+
+class UnblockPayload extends Assignable {}
+const UnblockPayloadSchema = new Map([
+    [
+        UnblockPayload,
+        {
+            kind: 'struct',
+            fields: [['id', 'u8']],
+        },
+    ],
+]);
+
+class ChangeReceiptAgePayload extends Assignable {}
+const ChangeReceiptAgePayloadSchema = new Map([
+    [
+        ChangeReceiptAgePayload,
+        {
+            kind: 'struct',
+            fields: [
+                ['id', 'u8'],
+                ['age', 'u64'],
+            ],
+        },
+    ],
+]);
+
+class ChangeDropOffPayload extends Assignable {}
+const ChangeDropOffPayloadSchema = new Map([
+    [
+        ChangeDropOffPayload,
+        {
+            kind: 'struct',
+            fields: [
+                ['id', 'u8'],
+                ['amount', 'u64'],
+                ['threshold', 'u64'],
+            ],
+        },
+    ],
+]);
+
+class CloseBridgeReceiptPayload extends Assignable {}
+const CloseBridgeReceiptPayloadSchema = new Map([
+    [
+        CloseBridgeReceiptPayload,
+        {
+            kind: 'struct',
+            fields: [['id', 'u8']],
+        },
+    ],
+]);
+
+class ChangeDecimalsPayload extends Assignable {}
+const ChangeDecimalsPayloadSchema = new Map([
+    [
+        ChangeDecimalsPayload,
+        {
+            kind: 'struct',
+            fields: [
+                ['id', 'u8'],
+                ['remote_decimals', 'u8'],
+            ],
+        },
+    ],
+]);
+
 class ChangeFeePayload extends Assignable {}
 const ChangeFeePayloadSchema = new Map([
     [
@@ -192,6 +579,9 @@ const CreatePayloadSchema = new Map([
                 ['destination', 'u32'],
                 ['max', 'u64'],
                 ['min', 'u64'],
+                ['mode', 'u8'],
+                ['remote_decimals', 'u8'],
+                ['recipient_format', 'u8'],
             ],
         },
     ],
@@ -205,6 +595,7 @@ const BridgePayloadSchema = new Map([
             kind: 'struct',
             fields: [
                 ['id', 'u8'],
+                ['destination', 'u32'],
                 ['amount', 'u64'],
                 ['recipient', 'string'],
                 ['payload', ['u8']],
@@ -213,20 +604,127 @@ const BridgePayloadSchema = new Map([
     ],
 ]);
 
+export class TransferId extends Assignable {}
+export const TransferIdSchema = new Map([
+    [
+        TransferId,
+        {
+            kind: 'struct',
+            fields: [
+                ['source_chain', 'u32'],
+                ['cashier', [20]],
+                ['index', 'u64'],
+            ],
+        },
+    ],
+]);
+
 class SettlePayload extends Assignable {}
-const SettlePayloadSchema = new Map([
+const SettlePayloadSchema = new Map<any, any>([
     [
         SettlePayload,
         {
             kind: 'struct',
             fields: [
                 ['id', 'u8'],
-                ['amount', 'u64'],
+                ['amount', 'u128'],
+                ['transfer_id', TransferId],
+            ],
+        },
+    ],
+    ...TransferIdSchema,
+]);
+
+class SettleAndCallPayload extends Assignable {}
+const SettleAndCallPayloadSchema = new Map<any, any>([
+    [
+        SettleAndCallPayload,
+        {
+            kind: 'struct',
+            fields: [
+                ['id', 'u8'],
+                ['amount', 'u128'],
+                ['transfer_id', TransferId],
+                ['sender', ['u8']],
+                ['payload', ['u8']],
+            ],
+        },
+    ],
+    ...TransferIdSchema,
+]);
+
+export class SettleEntry extends Assignable {}
+class SettleBatchPayload extends Assignable {}
+const SettleBatchPayloadSchema = new Map<any, any>([
+    [
+        SettleBatchPayload,
+        {
+            kind: 'struct',
+            fields: [
+                ['id', 'u8'],
+                ['entries', [SettleEntry]],
+            ],
+        },
+    ],
+    [
+        SettleEntry,
+        {
+            kind: 'struct',
+            fields: [
+                ['recipient_index', 'u8'],
+                ['amount', 'u128'],
+                ['transfer_id', TransferId],
             ],
         },
     ],
+    ...TransferIdSchema,
 ]);
 
+export function findCallerAddress(
+    cToken: PublicKey,
+    cTokenProgramId: PublicKey,
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from('caller'), cToken.toBuffer()],
+        cTokenProgramId,
+    );
+}
+
+// owner of the recipient account of SettleAndCall to receiverProgramId,
+// or the recipient itself for native cTokens
+export function findRecipientOwnerAddress(
+    receiverProgramId: PublicKey,
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from('ctoken')],
+        receiverProgramId,
+    );
+}
+
+export function findSettlementReceiptAddress(
+    config: PublicKey,
+    transferId: TransferId,
+    cTokenProgramId: PublicKey,
+): [PublicKey, number] {
+    const sourceChain = Buffer.alloc(4);
+    // @ts-ignore
+    sourceChain.writeUInt32LE(transferId.source_chain);
+    const index = Buffer.alloc(8);
+    // @ts-ignore
+    index.writeBigUInt64LE(BigInt(transferId.index));
+    return PublicKey.findProgramAddressSync(
+        [
+            Buffer.from('settlement'),
+            config.toBuffer(),
+            sourceChain,
+            // @ts-ignore
+            Buffer.from(transferId.cashier),
+            index,
+        ],
+        cTokenProgramId,
+    );
+}
+
 class ChangeLimitPayload extends Assignable {}
 const ChangeLimitPayloadSchema = new Map([
     [
@@ -242,33 +740,200 @@ const ChangeLimitPayloadSchema = new Map([
     ],
 ]);
 
-export class Config {
-    static async getMinBalanceRentForExemptCToken(
-        connection: Connection,
-    ): Promise<number> {
-        return await connection.getMinimumBalanceForRentExemption(
-            C_TOKEN_CONFIG_SIZE,
-        );
-    }
+class ChangeVolumeLimitPayload extends Assignable {}
+const ChangeVolumeLimitPayloadSchema = new Map([
+    [
+        ChangeVolumeLimitPayload,
+        {
+            kind: 'struct',
+            fields: [
+                ['id', 'u8'],
+                ['window', 'u64'],
+                ['outbound', 'u64'],
+                ['inbound', 'u64'],
+            ],
+        },
+    ],
+]);
 
-    static configInstruction(
-        config: PublicKey,
-        owner: PublicKey,
-        authority: PublicKey,
+class ChangeTokenFeePayload extends Assignable {}
+const ChangeTokenFeePayloadSchema = new Map([
+    [
+        ChangeTokenFeePayload,
+        {
+            kind: 'struct',
+            fields: [
+                ['id', 'u8'],
+                ['bps', 'u16'],
+                ['min', 'u64'],
+                ['max', 'u64'],
+            ],
+        },
+    ],
+]);
+
+class WithdrawTokenFeePayload extends Assignable {}
+const WithdrawTokenFeePayloadSchema = new Map([
+    [
+        WithdrawTokenFeePayload,
+        {
+            kind: 'struct',
+            fields: [
+                ['id', 'u8'],
+                ['amount', 'u64'],
+            ],
+        },
+    ],
+]);
+
+export function findRentVaultAddress(
+    config: PublicKey,
+    cTokenProgramId: PublicKey,
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from('rent'), config.toBuffer()],
+        cTokenProgramId,
+    );
+}
+
+export function findDropOffVaultAddress(
+    config: PublicKey,
+    cTokenProgramId: PublicKey,
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from('drop-off'), config.toBuffer()],
+        cTokenProgramId,
+    );
+}
+
+// accounts following the settle accounts of a cToken with a drop-off, wallet
+// being the recipient wallet or the owner of the recipient token account
+export function dropOffAccounts(
+    config: PublicKey,
+    wallet: PublicKey,
+    cTokenProgramId: PublicKey,
+): AccountMeta[] {
+    const [dropOffVault] = findDropOffVaultAddress(config, cTokenProgramId);
+    return [
+        {pubkey: wallet, isSigner: false, isWritable: true},
+        {pubkey: dropOffVault, isSigner: false, isWritable: true},
+    ];
+}
+
+export function findConfigAddress(
+    owner: PublicKey,
+    seed: string,
+    cTokenProgramId: PublicKey,
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from('config'), owner.toBuffer(), Buffer.from(seed)],
+        cTokenProgramId,
+    );
+}
+
+export function findCTokenAddress(
+    config: PublicKey,
+    tokenMint: PublicKey,
+    destination: number,
+    cTokenProgramId: PublicKey,
+): [PublicKey, number] {
+    const destinationBuffer = Buffer.alloc(4);
+    destinationBuffer.writeUInt32LE(destination);
+    return PublicKey.findProgramAddressSync(
+        [
+            Buffer.from('ctoken'),
+            config.toBuffer(),
+            tokenMint.toBuffer(),
+            destinationBuffer,
+        ],
+        cTokenProgramId,
+    );
+}
+
+export function findLiquidityProviderAddress(
+    cToken: PublicKey,
+    provider: PublicKey,
+    cTokenProgramId: PublicKey,
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from('liquidity'), cToken.toBuffer(), provider.toBuffer()],
+        cTokenProgramId,
+    );
+}
+
+export function findRouteAddress(
+    cToken: PublicKey,
+    destination: number,
+    cTokenProgramId: PublicKey,
+): [PublicKey, number] {
+    const destinationBuffer = Buffer.alloc(4);
+    destinationBuffer.writeUInt32LE(destination);
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from('route'), cToken.toBuffer(), destinationBuffer],
+        cTokenProgramId,
+    );
+}
+
+export function findBridgeReceiptAddress(
+    cToken: PublicKey,
+    index: bigint,
+    cTokenProgramId: PublicKey,
+): [PublicKey, number] {
+    const indexBuffer = Buffer.alloc(8);
+    indexBuffer.writeBigUInt64LE(index);
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from('bridge'), cToken.toBuffer(), indexBuffer],
+        cTokenProgramId,
+    );
+}
+
+export function findBlockedAccountAddress(
+    config: PublicKey,
+    account: PublicKey,
+    cTokenProgramId: PublicKey,
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from('blocked'), config.toBuffer(), account.toBuffer()],
+        cTokenProgramId,
+    );
+}
+
+export class Config {
+    static async getMinBalanceRentForExemptCToken(
+        connection: Connection,
+    ): Promise<number> {
+        return await connection.getMinimumBalanceForRentExemption(
+            C_TOKEN_CONFIG_SIZE,
+        );
+    }
+
+    static configInstruction(
+        config: PublicKey,
+        seed: string,
+        owner: PublicKey,
+        authority: PublicKey,
         fee: number,
         feeCollector: PublicKey,
+        payer: PublicKey,
         cTokenProgramId: PublicKey,
     ): TransactionInstruction {
         const keys = [
             {pubkey: config, isSigner: false, isWritable: true},
-            {pubkey: owner, isSigner: false, isWritable: false},
+            {pubkey: owner, isSigner: true, isWritable: false},
             {pubkey: authority, isSigner: false, isWritable: false},
             {pubkey: feeCollector, isSigner: false, isWritable: false},
+            {pubkey: payer, isSigner: true, isWritable: true},
+            {
+                pubkey: SystemProgram.programId,
+                isSigner: false,
+                isWritable: false,
+            },
         ];
 
         const data = new ConfigPayload({
             id: InstructionVariant.Config,
             fee: fee,
+            seed: seed,
         });
 
         return new TransactionInstruction({
@@ -328,49 +993,159 @@ export class Config {
         });
     }
 
+    // newOwner is the proposed owner, or the proposed authority if authority
+    static acceptInstruction(
+        config: PublicKey,
+        newOwner: PublicKey,
+        authority: boolean,
+        cTokenProgramId: PublicKey,
+    ): TransactionInstruction {
+        const keys = [
+            {pubkey: config, isSigner: false, isWritable: true},
+            {pubkey: newOwner, isSigner: true, isWritable: false},
+        ];
+
+        const data = new AcceptPayload({
+            id: authority
+                ? InstructionVariant.AcceptAuthority
+                : InstructionVariant.AcceptOwner,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(borsh.serialize(AcceptPayloadSchema, data)),
+        });
+    }
+
+    static cancelPendingInstruction(
+        config: PublicKey,
+        owner: PublicKey,
+        authority: boolean,
+        cTokenProgramId: PublicKey,
+    ): TransactionInstruction {
+        const keys = [
+            {pubkey: config, isSigner: false, isWritable: true},
+            {pubkey: owner, isSigner: true, isWritable: false},
+        ];
+
+        const data = new CancelPendingPayload({
+            id: authority
+                ? InstructionVariant.CancelPendingAuthority
+                : InstructionVariant.CancelPendingOwner,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(borsh.serialize(CancelPendingPayloadSchema, data)),
+        });
+    }
+
+    // migrates legacy config and cTokens under it to the current layout,
+    // cTokens are given with their token mint and cTokens created before
+    // decimals take remoteDecimals
+    static migrateInstruction(
+        config: PublicKey,
+        owner: PublicKey,
+        payer: PublicKey,
+        cTokens: [PublicKey, PublicKey][],
+        remoteDecimals: number,
+        cTokenProgramId: PublicKey,
+    ): TransactionInstruction {
+        const keys = [
+            {pubkey: config, isSigner: false, isWritable: true},
+            {pubkey: owner, isSigner: true, isWritable: false},
+            {pubkey: payer, isSigner: true, isWritable: true},
+            {
+                pubkey: SystemProgram.programId,
+                isSigner: false,
+                isWritable: false,
+            },
+        ];
+        for (const [cToken, tokenMint] of cTokens) {
+            keys.push({pubkey: cToken, isSigner: false, isWritable: true});
+            keys.push({pubkey: tokenMint, isSigner: false, isWritable: false});
+        }
+
+        const data = new MigratePayload({
+            id: InstructionVariant.Migrate,
+            remote_decimals: remoteDecimals,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(borsh.serialize(MigratePayloadSchema, data)),
+        });
+    }
+
+    // pauses the whole config, or only cToken when it is given
+    static pauseInstruction(
+        config: PublicKey,
+        owner: PublicKey,
+        cToken: PublicKey | null,
+        paused: boolean,
+        cTokenProgramId: PublicKey,
+        route?: PublicKey,
+    ): TransactionInstruction {
+        const keys = [
+            {pubkey: config, isSigner: false, isWritable: cToken === null},
+            {pubkey: owner, isSigner: true, isWritable: false},
+        ];
+        if (cToken !== null) {
+            keys.push({pubkey: cToken, isSigner: false, isWritable: !route});
+            // pauses only the route of cToken
+            if (route) {
+                keys.push({pubkey: route, isSigner: false, isWritable: true});
+            }
+        }
+
+        const data = new PausePayload({
+            id: paused ? InstructionVariant.Pause : InstructionVariant.Unpause,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(borsh.serialize(PausePayloadSchema, data)),
+        });
+    }
+
+    // payer is owner
     static async initialConfig(
         connection: Connection,
-        config: PublicKey,
         seed: string,
-        owner: PublicKey,
         authority: PublicKey,
         fee: number,
         feeCollector: PublicKey,
         payer: Keypair,
         cTokenProgramId: PublicKey,
         confirmOptions?: ConfirmOptions,
-    ): Promise<TransactionSignature> {
-        const balanceNeeded =
-            await Config.getMinBalanceRentForExemptCToken(connection);
-        const transaction = new Transaction();
-        transaction.add(
-            SystemProgram.createAccountWithSeed({
-                fromPubkey: payer.publicKey,
-                basePubkey: payer.publicKey,
-                seed: seed,
-                newAccountPubkey: config,
-                lamports: balanceNeeded,
-                space: C_TOKEN_CONFIG_SIZE,
-                programId: cTokenProgramId,
-            }),
-        );
-
-        const instruction = Config.configInstruction(
-            config,
-            owner,
-            authority,
-            fee,
-            feeCollector,
+    ): Promise<[PublicKey, TransactionSignature]> {
+        const [config] = findConfigAddress(
+            payer.publicKey,
+            seed,
             cTokenProgramId,
         );
-
-        transaction.add(instruction);
-        return await sendAndConfirmTransaction(
+        const signature = await sendAndConfirmTransaction(
             connection,
-            transaction,
+            new Transaction().add(
+                Config.configInstruction(
+                    config,
+                    seed,
+                    payer.publicKey,
+                    authority,
+                    fee,
+                    feeCollector,
+                    payer.publicKey,
+                    cTokenProgramId,
+                ),
+            ),
             [payer],
             confirmOptions,
         );
+        return [config, signature];
     }
 
     static async transferOwner(
@@ -418,6 +1193,106 @@ export class Config {
             confirmOptions,
         );
     }
+
+    static async accept(
+        connection: Connection,
+        config: PublicKey,
+        authority: boolean,
+        cTokenProgramId: PublicKey,
+        newOwner: Keypair,
+        confirmOptions?: ConfirmOptions,
+    ): Promise<TransactionSignature> {
+        return await sendAndConfirmTransaction(
+            connection,
+            new Transaction().add(
+                Config.acceptInstruction(
+                    config,
+                    newOwner.publicKey,
+                    authority,
+                    cTokenProgramId,
+                ),
+            ),
+            [newOwner],
+            confirmOptions,
+        );
+    }
+
+    static async cancelPending(
+        connection: Connection,
+        config: PublicKey,
+        authority: boolean,
+        cTokenProgramId: PublicKey,
+        owner: Keypair,
+        confirmOptions?: ConfirmOptions,
+    ): Promise<TransactionSignature> {
+        return await sendAndConfirmTransaction(
+            connection,
+            new Transaction().add(
+                Config.cancelPendingInstruction(
+                    config,
+                    owner.publicKey,
+                    authority,
+                    cTokenProgramId,
+                ),
+            ),
+            [owner],
+            confirmOptions,
+        );
+    }
+
+    // payer is owner
+    static async migrate(
+        connection: Connection,
+        config: PublicKey,
+        cTokens: [PublicKey, PublicKey][],
+        remoteDecimals: number,
+        cTokenProgramId: PublicKey,
+        payer: Keypair,
+        confirmOptions?: ConfirmOptions,
+    ): Promise<TransactionSignature> {
+        return await sendAndConfirmTransaction(
+            connection,
+            new Transaction().add(
+                Config.migrateInstruction(
+                    config,
+                    payer.publicKey,
+                    payer.publicKey,
+                    cTokens,
+                    remoteDecimals,
+                    cTokenProgramId,
+                ),
+            ),
+            [payer],
+            confirmOptions,
+        );
+    }
+
+    static async setPaused(
+        connection: Connection,
+        config: PublicKey,
+        cToken: PublicKey | null,
+        paused: boolean,
+        cTokenProgramId: PublicKey,
+        owner: Keypair,
+        confirmOptions?: ConfirmOptions,
+        route?: PublicKey,
+    ): Promise<TransactionSignature> {
+        return await sendAndConfirmTransaction(
+            connection,
+            new Transaction().add(
+                Config.pauseInstruction(
+                    config,
+                    owner.publicKey,
+                    cToken,
+                    paused,
+                    cTokenProgramId,
+                    route,
+                ),
+            ),
+            [owner],
+            confirmOptions,
+        );
+    }
 }
 
 export class CToken {
@@ -437,19 +1312,34 @@ export class CToken {
         tokenProgramId: PublicKey,
         owner: PublicKey,
         config: PublicKey,
+        payer: PublicKey,
         destination: number,
         max: number | bigint,
         min: number | bigint,
+        mode: CTokenMode,
+        remoteDecimals: number,
+        recipientFormat: RecipientFormat,
         cTokenProgramId: PublicKey,
     ): TransactionInstruction {
         const keys = [
             {pubkey: cToken, isSigner: false, isWritable: true},
-            {pubkey: authority, isSigner: false, isWritable: false},
+            // native cTokens fund the authority as vault
+            {
+                pubkey: authority,
+                isSigner: false,
+                isWritable: mode === CTokenMode.Native,
+            },
             {pubkey: tokenMint, isSigner: false, isWritable: false},
             {pubkey: tokenAccount, isSigner: false, isWritable: false},
             {pubkey: tokenProgramId, isSigner: false, isWritable: false},
             {pubkey: owner, isSigner: true, isWritable: false},
             {pubkey: config, isSigner: false, isWritable: false},
+            {pubkey: payer, isSigner: true, isWritable: true},
+            {
+                pubkey: SystemProgram.programId,
+                isSigner: false,
+                isWritable: false,
+            },
         ];
 
         const createData = new CreatePayload({
@@ -457,6 +1347,9 @@ export class CToken {
             destination: destination,
             max: max,
             min: min,
+            mode: mode,
+            remote_decimals: remoteDecimals,
+            recipient_format: recipientFormat,
         });
 
         return new TransactionInstruction({
@@ -472,13 +1365,24 @@ export class CToken {
         cTokenTokenAccount: PublicKey,
         userAccount: PublicKey,
         userTransferAuthority: PublicKey,
+        userOwner: PublicKey,
         tokenMint: PublicKey,
         tokenProgramInfo: PublicKey,
+        destination: number,
         amount: bigint,
         recipient: string,
         payload: number[],
         cTokenProgramId: PublicKey,
+        feeToken?: PublicKey,
+        route?: PublicKey,
+        extraAccounts: AccountMeta[] = [],
+        receipt?: {payer: PublicKey; index: bigint},
     ): TransactionInstruction {
+        const [blockedAccount] = findBlockedAccountAddress(
+            config,
+            userOwner,
+            cTokenProgramId,
+        );
         const keys = [
             {pubkey: cToken, isSigner: false, isWritable: true},
             {pubkey: cTokenTokenAccount, isSigner: false, isWritable: true},
@@ -487,10 +1391,53 @@ export class CToken {
             {pubkey: tokenMint, isSigner: false, isWritable: true},
             {pubkey: tokenProgramInfo, isSigner: false, isWritable: false},
             {pubkey: config, isSigner: false, isWritable: false},
+            {pubkey: blockedAccount, isSigner: false, isWritable: false},
         ];
+        // blocked account of a delegate signing for the owner
+        if (!userTransferAuthority.equals(userOwner)) {
+            const [authorityBlockedAccount] = findBlockedAccountAddress(
+                config,
+                userTransferAuthority,
+                cTokenProgramId,
+            );
+            keys.push({
+                pubkey: authorityBlockedAccount,
+                isSigner: false,
+                isWritable: false,
+            });
+        }
+        // route of cToken, required if destination is not the cToken destination
+        if (route) {
+            keys.push({pubkey: route, isSigner: false, isWritable: false});
+        }
+        // token fee account of cToken, required if cToken charges token fee
+        if (feeToken) {
+            keys.push({pubkey: feeToken, isSigner: false, isWritable: true});
+        }
+        // bridge receipt, required if cToken has a receipt age, at the
+        // cToken index after the bridge
+        if (receipt) {
+            const [receiptAccount] = findBridgeReceiptAddress(
+                cToken,
+                receipt.index,
+                cTokenProgramId,
+            );
+            keys.push(
+                {pubkey: receiptAccount, isSigner: false, isWritable: true},
+                {pubkey: receipt.payer, isSigner: true, isWritable: true},
+                {
+                    pubkey: SystemProgram.programId,
+                    isSigner: false,
+                    isWritable: false,
+                },
+            );
+        }
+        // memo program and transfer hook accounts of Token-2022 mints
+        keys.push(...extraAccounts);
 
         const bridgeData = new BridgePayload({
             id: InstructionVariant.Bridge,
+            destination: destination,
             amount: amount,
             recipient: recipient,
             payload: payload,
@@ -509,14 +1456,28 @@ export class CToken {
         tokenAuthority: PublicKey,
         cTokenTokenAccount: PublicKey,
         userAccount: PublicKey,
+        userOwner: PublicKey,
         authority: PublicKey,
         tokenMint: PublicKey,
         tokenProgramInfo: PublicKey,
+        payer: PublicKey,
         amount: bigint,
+        transferId: TransferId,
         cTokenProgramId: PublicKey,
+        extraAccounts: AccountMeta[] = [],
     ): TransactionInstruction {
+        const [receipt] = findSettlementReceiptAddress(
+            config,
+            transferId,
+            cTokenProgramId,
+        );
+        const [blockedAccount] = findBlockedAccountAddress(
+            config,
+            userOwner,
+            cTokenProgramId,
+        );
         const keys = [
-            {pubkey: cToken, isSigner: false, isWritable: false},
+            {pubkey: cToken, isSigner: false, isWritable: true},
             {pubkey: tokenAuthority, isSigner: false, isWritable: false},
             {pubkey: cTokenTokenAccount, isSigner: false, isWritable: true},
             {pubkey: userAccount, isSigner: false, isWritable: true},
@@ -524,11 +1485,21 @@ export class CToken {
             {pubkey: tokenMint, isSigner: false, isWritable: true},
             {pubkey: tokenProgramInfo, isSigner: false, isWritable: false},
             {pubkey: config, isSigner: false, isWritable: false},
+            {pubkey: receipt, isSigner: false, isWritable: true},
+            {pubkey: payer, isSigner: true, isWritable: true},
+            {
+                pubkey: SystemProgram.programId,
+                isSigner: false,
+                isWritable: false,
+            },
+            {pubkey: blockedAccount, isSigner: false, isWritable: false},
+            ...extraAccounts,
         ];
 
         const settleData = new SettlePayload({
             id: InstructionVariant.Settle,
             amount: amount,
+            transfer_id: transferId,
         });
 
         return new TransactionInstruction({
@@ -538,22 +1509,220 @@ export class CToken {
         });
     }
 
-    static changeLimitInstruction(
+    // settles to the associated token account of wallet, created when
+    // missing with rent from the config rent vault or the payer
+    static settleToWalletInstruction(
         cToken: PublicKey,
         config: PublicKey,
-        owner: PublicKey,
-        max: bigint,
-        min: bigint,
+        tokenAuthority: PublicKey,
+        cTokenTokenAccount: PublicKey,
+        wallet: PublicKey,
+        authority: PublicKey,
+        tokenMint: PublicKey,
+        tokenProgramInfo: PublicKey,
+        payer: PublicKey,
+        amount: bigint,
+        transferId: TransferId,
         cTokenProgramId: PublicKey,
+        extraAccounts: AccountMeta[] = [],
     ): TransactionInstruction {
-        const keys = [
-            {pubkey: config, isSigner: false, isWritable: false},
-            {pubkey: cToken, isSigner: false, isWritable: true},
-            {pubkey: owner, isSigner: true, isWritable: false},
-        ];
+        const recipient = getAssociatedTokenAddressSync(
+            tokenMint,
+            wallet,
+            true,
+            tokenProgramInfo,
+        );
+        const [rentVault] = findRentVaultAddress(config, cTokenProgramId);
+        return CToken.settleInstruction(
+            cToken,
+            config,
+            tokenAuthority,
+            cTokenTokenAccount,
+            wallet,
+            wallet,
+            authority,
+            tokenMint,
+            tokenProgramInfo,
+            payer,
+            amount,
+            transferId,
+            cTokenProgramId,
+            [
+                {pubkey: recipient, isSigner: false, isWritable: true},
+                {
+                    pubkey: ASSOCIATED_TOKEN_PROGRAM_ID,
+                    isSigner: false,
+                    isWritable: false,
+                },
+                {pubkey: rentVault, isSigner: false, isWritable: true},
+                ...extraAccounts,
+            ],
+        );
+    }
 
-        const data = new ChangeLimitPayload({
-            id: InstructionVariant.ChangeLimit,
+    static settleAndCallInstruction(
+        cToken: PublicKey,
+        config: PublicKey,
+        tokenAuthority: PublicKey,
+        cTokenTokenAccount: PublicKey,
+        userAccount: PublicKey,
+        userOwner: PublicKey,
+        authority: PublicKey,
+        tokenMint: PublicKey,
+        tokenProgramInfo: PublicKey,
+        payer: PublicKey,
+        receiverProgramId: PublicKey,
+        amount: bigint,
+        transferId: TransferId,
+        sender: Uint8Array,
+        payload: Uint8Array,
+        cTokenProgramId: PublicKey,
+        extraAccounts: AccountMeta[] = [],
+    ): TransactionInstruction {
+        const settle = CToken.settleInstruction(
+            cToken,
+            config,
+            tokenAuthority,
+            cTokenTokenAccount,
+            userAccount,
+            userOwner,
+            authority,
+            tokenMint,
+            tokenProgramInfo,
+            payer,
+            amount,
+            transferId,
+            cTokenProgramId,
+        );
+        const [caller] = findCallerAddress(cToken, cTokenProgramId);
+        const keys = [
+            ...settle.keys,
+            {pubkey: receiverProgramId, isSigner: false, isWritable: false},
+            {pubkey: caller, isSigner: false, isWritable: false},
+            ...extraAccounts,
+        ];
+
+        const data = new SettleAndCallPayload({
+            id: InstructionVariant.SettleAndCall,
+            amount: amount,
+            transfer_id: transferId,
+            sender: sender,
+            payload: payload,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(
+                borsh.serialize(SettleAndCallPayloadSchema, data),
+            ),
+        });
+    }
+
+    // settles each entry to the recipient at its recipient index, given as
+    // [account, owner] pairs, a wallet recipient of a lock or mint cToken
+    // being its own owner and paid to its associated token account
+    static settleBatchInstruction(
+        cToken: PublicKey,
+        config: PublicKey,
+        tokenAuthority: PublicKey,
+        cTokenTokenAccount: PublicKey,
+        authority: PublicKey,
+        tokenMint: PublicKey,
+        tokenProgramInfo: PublicKey,
+        payer: PublicKey,
+        recipients: [PublicKey, PublicKey][],
+        entries: SettleEntry[],
+        cTokenProgramId: PublicKey,
+        extraAccounts: AccountMeta[] = [],
+    ): TransactionInstruction {
+        const keys = [
+            {pubkey: cToken, isSigner: false, isWritable: true},
+            {pubkey: tokenAuthority, isSigner: false, isWritable: false},
+            {pubkey: cTokenTokenAccount, isSigner: false, isWritable: true},
+            {pubkey: authority, isSigner: true, isWritable: false},
+            {pubkey: tokenMint, isSigner: false, isWritable: true},
+            {pubkey: tokenProgramInfo, isSigner: false, isWritable: false},
+            {pubkey: config, isSigner: false, isWritable: false},
+            {pubkey: payer, isSigner: true, isWritable: true},
+            {
+                pubkey: SystemProgram.programId,
+                isSigner: false,
+                isWritable: false,
+            },
+            {
+                pubkey: ASSOCIATED_TOKEN_PROGRAM_ID,
+                isSigner: false,
+                isWritable: false,
+            },
+            {
+                pubkey: findRentVaultAddress(config, cTokenProgramId)[0],
+                isSigner: false,
+                isWritable: true,
+            },
+        ];
+        for (const entry of entries) {
+            const [receipt] = findSettlementReceiptAddress(
+                config,
+                entry.transfer_id,
+                cTokenProgramId,
+            );
+            keys.push({pubkey: receipt, isSigner: false, isWritable: true});
+        }
+        for (const [recipient, owner] of recipients) {
+            const [blockedAccount] = findBlockedAccountAddress(
+                config,
+                owner,
+                cTokenProgramId,
+            );
+            const tokenAccount =
+                recipient.equals(owner) &&
+                !tokenProgramInfo.equals(SystemProgram.programId)
+                    ? getAssociatedTokenAddressSync(
+                          tokenMint,
+                          owner,
+                          true,
+                          tokenProgramInfo,
+                      )
+                    : recipient;
+            keys.push({pubkey: recipient, isSigner: false, isWritable: true});
+            keys.push({pubkey: tokenAccount, isSigner: false, isWritable: true});
+            keys.push({
+                pubkey: blockedAccount,
+                isSigner: false,
+                isWritable: false,
+            });
+        }
+        keys.push(...extraAccounts);
+
+        const data = new SettleBatchPayload({
+            id: InstructionVariant.SettleBatch,
+            entries: entries,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(borsh.serialize(SettleBatchPayloadSchema, data)),
+        });
+    }
+
+    static changeLimitInstruction(
+        cToken: PublicKey,
+        config: PublicKey,
+        owner: PublicKey,
+        max: bigint,
+        min: bigint,
+        cTokenProgramId: PublicKey,
+    ): TransactionInstruction {
+        const keys = [
+            {pubkey: config, isSigner: false, isWritable: false},
+            {pubkey: cToken, isSigner: false, isWritable: true},
+            {pubkey: owner, isSigner: true, isWritable: false},
+        ];
+
+        const data = new ChangeLimitPayload({
+            id: InstructionVariant.ChangeLimit,
             max: max,
             min: min,
         });
@@ -561,14 +1730,662 @@ export class CToken {
         return new TransactionInstruction({
             keys,
             programId: cTokenProgramId,
-            data: Buffer.from(borsh.serialize(ChangeLimitPayloadSchema, data)),
+            data: Buffer.from(borsh.serialize(ChangeLimitPayloadSchema, data)),
+        });
+    }
+
+    static changeVolumeLimitInstruction(
+        cToken: PublicKey,
+        config: PublicKey,
+        owner: PublicKey,
+        window: number,
+        outbound: bigint,
+        inbound: bigint,
+        cTokenProgramId: PublicKey,
+    ): TransactionInstruction {
+        const keys = [
+            {pubkey: config, isSigner: false, isWritable: false},
+            {pubkey: cToken, isSigner: false, isWritable: true},
+            {pubkey: owner, isSigner: true, isWritable: false},
+        ];
+
+        const data = new ChangeVolumeLimitPayload({
+            id: InstructionVariant.ChangeVolumeLimit,
+            window: window,
+            outbound: outbound,
+            inbound: inbound,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(
+                borsh.serialize(ChangeVolumeLimitPayloadSchema, data),
+            ),
+        });
+    }
+
+    static changeTokenFeeInstruction(
+        cToken: PublicKey,
+        config: PublicKey,
+        owner: PublicKey,
+        bps: number,
+        min: bigint,
+        max: bigint,
+        feeToken: PublicKey | null,
+        cTokenProgramId: PublicKey,
+    ): TransactionInstruction {
+        const keys = [
+            {pubkey: config, isSigner: false, isWritable: false},
+            {pubkey: cToken, isSigner: false, isWritable: true},
+            {pubkey: owner, isSigner: true, isWritable: false},
+        ];
+        if (feeToken !== null) {
+            keys.push({pubkey: feeToken, isSigner: false, isWritable: false});
+        }
+
+        const data = new ChangeTokenFeePayload({
+            id: InstructionVariant.ChangeTokenFee,
+            bps: bps,
+            min: min,
+            max: max,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(
+                borsh.serialize(ChangeTokenFeePayloadSchema, data),
+            ),
+        });
+    }
+
+    static withdrawTokenFeeInstruction(
+        cToken: PublicKey,
+        config: PublicKey,
+        owner: PublicKey,
+        tokenAuthority: PublicKey,
+        feeToken: PublicKey,
+        destination: PublicKey,
+        tokenMint: PublicKey,
+        tokenProgramId: PublicKey,
+        amount: bigint,
+        cTokenProgramId: PublicKey,
+    ): TransactionInstruction {
+        const keys = [
+            {pubkey: config, isSigner: false, isWritable: false},
+            {pubkey: cToken, isSigner: false, isWritable: false},
+            {pubkey: owner, isSigner: true, isWritable: false},
+            {pubkey: tokenAuthority, isSigner: false, isWritable: false},
+            {pubkey: feeToken, isSigner: false, isWritable: true},
+            {pubkey: destination, isSigner: false, isWritable: true},
+            {pubkey: tokenMint, isSigner: false, isWritable: false},
+            {pubkey: tokenProgramId, isSigner: false, isWritable: false},
+        ];
+
+        const data = new WithdrawTokenFeePayload({
+            id: InstructionVariant.WithdrawTokenFee,
+            amount: amount,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(
+                borsh.serialize(WithdrawTokenFeePayloadSchema, data),
+            ),
+        });
+    }
+
+    static withdrawRentVaultInstruction(
+        config: PublicKey,
+        owner: PublicKey,
+        destination: PublicKey,
+        amount: bigint,
+        cTokenProgramId: PublicKey,
+    ): TransactionInstruction {
+        const [rentVault] = findRentVaultAddress(config, cTokenProgramId);
+        const keys = [
+            {pubkey: config, isSigner: false, isWritable: false},
+            {pubkey: owner, isSigner: true, isWritable: false},
+            {pubkey: rentVault, isSigner: false, isWritable: true},
+            {pubkey: destination, isSigner: false, isWritable: true},
+            {
+                pubkey: SystemProgram.programId,
+                isSigner: false,
+                isWritable: false,
+            },
+        ];
+
+        const data = new WithdrawTokenFeePayload({
+            id: InstructionVariant.WithdrawRentVault,
+            amount: amount,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(
+                borsh.serialize(WithdrawTokenFeePayloadSchema, data),
+            ),
+        });
+    }
+
+    // destination receives the vault balance of a lock cToken,
+    // newMintAuthority receives the mint authority of a mint cToken
+    static closeCTokenInstruction(
+        cToken: PublicKey,
+        config: PublicKey,
+        owner: PublicKey,
+        tokenAuthority: PublicKey,
+        tokenAccount: PublicKey,
+        destination: PublicKey,
+        tokenMint: PublicKey,
+        newMintAuthority: PublicKey,
+        tokenProgramId: PublicKey,
+        receiver: PublicKey,
+        cTokenProgramId: PublicKey,
+        feeToken?: PublicKey,
+    ): TransactionInstruction {
+        const keys = [
+            {pubkey: config, isSigner: false, isWritable: false},
+            {pubkey: cToken, isSigner: false, isWritable: true},
+            {pubkey: owner, isSigner: true, isWritable: false},
+            {pubkey: tokenAuthority, isSigner: false, isWritable: false},
+            {pubkey: tokenAccount, isSigner: false, isWritable: true},
+            {pubkey: destination, isSigner: false, isWritable: true},
+            {pubkey: tokenMint, isSigner: false, isWritable: true},
+            {pubkey: newMintAuthority, isSigner: false, isWritable: false},
+            {pubkey: tokenProgramId, isSigner: false, isWritable: false},
+            {pubkey: receiver, isSigner: false, isWritable: true},
+        ];
+        // required, and must be empty, when a lock or mint cToken has a fee token account
+        if (feeToken) {
+            keys.push({pubkey: feeToken, isSigner: false, isWritable: false});
+        }
+
+        const data = new CloseCTokenPayload({
+            id: InstructionVariant.CloseCToken,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(borsh.serialize(CloseCTokenPayloadSchema, data)),
+        });
+    }
+
+    static setLiquidityProviderInstruction(
+        cToken: PublicKey,
+        config: PublicKey,
+        owner: PublicKey,
+        provider: PublicKey,
+        enabled: boolean,
+        payer: PublicKey,
+        cTokenProgramId: PublicKey,
+    ): TransactionInstruction {
+        const [liquidityProvider] = findLiquidityProviderAddress(
+            cToken,
+            provider,
+            cTokenProgramId,
+        );
+        const keys = [
+            {pubkey: config, isSigner: false, isWritable: false},
+            {pubkey: cToken, isSigner: false, isWritable: false},
+            {pubkey: owner, isSigner: true, isWritable: false},
+            {pubkey: provider, isSigner: false, isWritable: false},
+            {pubkey: liquidityProvider, isSigner: false, isWritable: true},
+            {pubkey: payer, isSigner: true, isWritable: true},
+            {
+                pubkey: SystemProgram.programId,
+                isSigner: false,
+                isWritable: false,
+            },
+        ];
+
+        const data = new SetLiquidityProviderPayload({
+            id: InstructionVariant.SetLiquidityProvider,
+            enabled: enabled ? 1 : 0,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(
+                borsh.serialize(SetLiquidityProviderPayloadSchema, data),
+            ),
+        });
+    }
+
+    static addLiquidityInstruction(
+        cToken: PublicKey,
+        config: PublicKey,
+        provider: PublicKey,
+        source: PublicKey,
+        tokenAccount: PublicKey,
+        tokenMint: PublicKey,
+        tokenProgramId: PublicKey,
+        amount: bigint,
+        cTokenProgramId: PublicKey,
+    ): TransactionInstruction {
+        const [liquidityProvider] = findLiquidityProviderAddress(
+            cToken,
+            provider,
+            cTokenProgramId,
+        );
+        const keys = [
+            {pubkey: cToken, isSigner: false, isWritable: true},
+            {pubkey: liquidityProvider, isSigner: false, isWritable: true},
+            {pubkey: provider, isSigner: true, isWritable: false},
+            {pubkey: source, isSigner: false, isWritable: true},
+            {pubkey: tokenAccount, isSigner: false, isWritable: true},
+            {pubkey: tokenMint, isSigner: false, isWritable: false},
+            {pubkey: tokenProgramId, isSigner: false, isWritable: false},
+            {pubkey: config, isSigner: false, isWritable: false},
+        ];
+
+        const data = new LiquidityPayload({
+            id: InstructionVariant.AddLiquidity,
+            amount: amount,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(borsh.serialize(LiquidityPayloadSchema, data)),
+        });
+    }
+
+    static removeLiquidityInstruction(
+        cToken: PublicKey,
+        config: PublicKey,
+        provider: PublicKey,
+        tokenAuthority: PublicKey,
+        tokenAccount: PublicKey,
+        destination: PublicKey,
+        tokenMint: PublicKey,
+        tokenProgramId: PublicKey,
+        amount: bigint,
+        cTokenProgramId: PublicKey,
+    ): TransactionInstruction {
+        const [liquidityProvider] = findLiquidityProviderAddress(
+            cToken,
+            provider,
+            cTokenProgramId,
+        );
+        const keys = [
+            {pubkey: cToken, isSigner: false, isWritable: true},
+            {pubkey: liquidityProvider, isSigner: false, isWritable: true},
+            {pubkey: provider, isSigner: true, isWritable: false},
+            {pubkey: tokenAuthority, isSigner: false, isWritable: false},
+            {pubkey: tokenAccount, isSigner: false, isWritable: true},
+            {pubkey: destination, isSigner: false, isWritable: true},
+            {pubkey: tokenMint, isSigner: false, isWritable: false},
+            {pubkey: tokenProgramId, isSigner: false, isWritable: false},
+            {pubkey: config, isSigner: false, isWritable: false},
+        ];
+
+        const data = new LiquidityPayload({
+            id: InstructionVariant.RemoveLiquidity,
+            amount: amount,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(borsh.serialize(LiquidityPayloadSchema, data)),
+        });
+    }
+
+    static changeLiquidityReserveInstruction(
+        cToken: PublicKey,
+        config: PublicKey,
+        owner: PublicKey,
+        reserve: bigint,
+        cTokenProgramId: PublicKey,
+    ): TransactionInstruction {
+        const keys = [
+            {pubkey: config, isSigner: false, isWritable: false},
+            {pubkey: cToken, isSigner: false, isWritable: true},
+            {pubkey: owner, isSigner: true, isWritable: false},
+        ];
+
+        const data = new ChangeLiquidityReservePayload({
+            id: InstructionVariant.ChangeLiquidityReserve,
+            reserve: reserve,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(
+                borsh.serialize(ChangeLiquidityReservePayloadSchema, data),
+            ),
+        });
+    }
+
+    // adds a route, or updates an existing one
+    static addRouteInstruction(
+        cToken: PublicKey,
+        config: PublicKey,
+        owner: PublicKey,
+        destination: number,
+        max: bigint,
+        min: bigint,
+        fee: bigint,
+        recipientFormat: RecipientFormat,
+        payer: PublicKey,
+        cTokenProgramId: PublicKey,
+    ): TransactionInstruction {
+        const [route] = findRouteAddress(cToken, destination, cTokenProgramId);
+        const keys = [
+            {pubkey: config, isSigner: false, isWritable: false},
+            {pubkey: cToken, isSigner: false, isWritable: false},
+            {pubkey: owner, isSigner: true, isWritable: false},
+            {pubkey: route, isSigner: false, isWritable: true},
+            {pubkey: payer, isSigner: true, isWritable: true},
+            {
+                pubkey: SystemProgram.programId,
+                isSigner: false,
+                isWritable: false,
+            },
+        ];
+
+        const data = new AddRoutePayload({
+            id: InstructionVariant.AddRoute,
+            destination: destination,
+            max: max,
+            min: min,
+            fee: fee,
+            recipient_format: recipientFormat,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(borsh.serialize(AddRoutePayloadSchema, data)),
+        });
+    }
+
+    static removeRouteInstruction(
+        cToken: PublicKey,
+        config: PublicKey,
+        owner: PublicKey,
+        destination: number,
+        receiver: PublicKey,
+        cTokenProgramId: PublicKey,
+    ): TransactionInstruction {
+        const [route] = findRouteAddress(cToken, destination, cTokenProgramId);
+        const keys = [
+            {pubkey: config, isSigner: false, isWritable: false},
+            {pubkey: cToken, isSigner: false, isWritable: false},
+            {pubkey: owner, isSigner: true, isWritable: false},
+            {pubkey: route, isSigner: false, isWritable: true},
+            {pubkey: receiver, isSigner: false, isWritable: true},
+        ];
+
+        const data = new RemoveRoutePayload({
+            id: InstructionVariant.RemoveRoute,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(borsh.serialize(RemoveRoutePayloadSchema, data)),
+        });
+    }
+
+    // sets the remote decimals and rereads the mint decimals
+    static changeDecimalsInstruction(
+        cToken: PublicKey,
+        config: PublicKey,
+        owner: PublicKey,
+        tokenMint: PublicKey,
+        remoteDecimals: number,
+        cTokenProgramId: PublicKey,
+    ): TransactionInstruction {
+        const keys = [
+            {pubkey: config, isSigner: false, isWritable: false},
+            {pubkey: cToken, isSigner: false, isWritable: true},
+            {pubkey: owner, isSigner: true, isWritable: false},
+            {pubkey: tokenMint, isSigner: false, isWritable: false},
+        ];
+
+        const data = new ChangeDecimalsPayload({
+            id: InstructionVariant.ChangeDecimals,
+            remote_decimals: remoteDecimals,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(
+                borsh.serialize(ChangeDecimalsPayloadSchema, data),
+            ),
+        });
+    }
+
+    // bridge receipts can be closed after age seconds, 0 stops writing them
+    static changeReceiptAgeInstruction(
+        cToken: PublicKey,
+        config: PublicKey,
+        owner: PublicKey,
+        age: bigint,
+        cTokenProgramId: PublicKey,
+    ): TransactionInstruction {
+        const keys = [
+            {pubkey: config, isSigner: false, isWritable: false},
+            {pubkey: cToken, isSigner: false, isWritable: true},
+            {pubkey: owner, isSigner: true, isWritable: false},
+        ];
+
+        const data = new ChangeReceiptAgePayload({
+            id: InstructionVariant.ChangeReceiptAge,
+            age: age,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(
+                borsh.serialize(ChangeReceiptAgePayloadSchema, data),
+            ),
+        });
+    }
+
+    static changeDropOffInstruction(
+        cToken: PublicKey,
+        config: PublicKey,
+        owner: PublicKey,
+        amount: bigint,
+        threshold: bigint,
+        cTokenProgramId: PublicKey,
+    ): TransactionInstruction {
+        const keys = [
+            {pubkey: config, isSigner: false, isWritable: false},
+            {pubkey: cToken, isSigner: false, isWritable: true},
+            {pubkey: owner, isSigner: true, isWritable: false},
+        ];
+
+        const data = new ChangeDropOffPayload({
+            id: InstructionVariant.ChangeDropOff,
+            amount: amount,
+            threshold: threshold,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(borsh.serialize(ChangeDropOffPayloadSchema, data)),
+        });
+    }
+
+    static fundDropOffVaultInstruction(
+        config: PublicKey,
+        owner: PublicKey,
+        amount: bigint,
+        cTokenProgramId: PublicKey,
+    ): TransactionInstruction {
+        const [dropOffVault] = findDropOffVaultAddress(config, cTokenProgramId);
+        const keys = [
+            {pubkey: config, isSigner: false, isWritable: false},
+            {pubkey: owner, isSigner: true, isWritable: true},
+            {pubkey: dropOffVault, isSigner: false, isWritable: true},
+            {
+                pubkey: SystemProgram.programId,
+                isSigner: false,
+                isWritable: false,
+            },
+        ];
+
+        const data = new WithdrawTokenFeePayload({
+            id: InstructionVariant.FundDropOffVault,
+            amount: amount,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(
+                borsh.serialize(WithdrawTokenFeePayloadSchema, data),
+            ),
+        });
+    }
+
+    static withdrawDropOffVaultInstruction(
+        config: PublicKey,
+        owner: PublicKey,
+        destination: PublicKey,
+        amount: bigint,
+        cTokenProgramId: PublicKey,
+    ): TransactionInstruction {
+        const [dropOffVault] = findDropOffVaultAddress(config, cTokenProgramId);
+        const keys = [
+            {pubkey: config, isSigner: false, isWritable: false},
+            {pubkey: owner, isSigner: true, isWritable: false},
+            {pubkey: dropOffVault, isSigner: false, isWritable: true},
+            {pubkey: destination, isSigner: false, isWritable: true},
+            {
+                pubkey: SystemProgram.programId,
+                isSigner: false,
+                isWritable: false,
+            },
+        ];
+
+        const data = new WithdrawTokenFeePayload({
+            id: InstructionVariant.WithdrawDropOffVault,
+            amount: amount,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(
+                borsh.serialize(WithdrawTokenFeePayloadSchema, data),
+            ),
+        });
+    }
+
+    // anyone can close an expired bridge receipt, refunding its payer
+    static closeBridgeReceiptInstruction(
+        cToken: PublicKey,
+        index: bigint,
+        payer: PublicKey,
+        cTokenProgramId: PublicKey,
+    ): TransactionInstruction {
+        const [receipt] = findBridgeReceiptAddress(
+            cToken,
+            index,
+            cTokenProgramId,
+        );
+        const keys = [
+            {pubkey: cToken, isSigner: false, isWritable: true},
+            {pubkey: receipt, isSigner: false, isWritable: true},
+            {pubkey: payer, isSigner: false, isWritable: true},
+        ];
+
+        const data = new CloseBridgeReceiptPayload({
+            id: InstructionVariant.CloseBridgeReceipt,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(
+                borsh.serialize(CloseBridgeReceiptPayloadSchema, data),
+            ),
+        });
+    }
+
+    // blocks an account from bridging and receiving settlements
+    static blockInstruction(
+        config: PublicKey,
+        owner: PublicKey,
+        account: PublicKey,
+        payer: PublicKey,
+        cTokenProgramId: PublicKey,
+    ): TransactionInstruction {
+        const [blockedAccount] = findBlockedAccountAddress(
+            config,
+            account,
+            cTokenProgramId,
+        );
+        const keys = [
+            {pubkey: config, isSigner: false, isWritable: false},
+            {pubkey: owner, isSigner: true, isWritable: false},
+            {pubkey: blockedAccount, isSigner: false, isWritable: true},
+            {pubkey: payer, isSigner: true, isWritable: true},
+            {
+                pubkey: SystemProgram.programId,
+                isSigner: false,
+                isWritable: false,
+            },
+        ];
+
+        const data = new BlockPayload({
+            id: InstructionVariant.Block,
+            account: account.toBytes(),
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(borsh.serialize(BlockPayloadSchema, data)),
+        });
+    }
+
+    static unblockInstruction(
+        config: PublicKey,
+        owner: PublicKey,
+        account: PublicKey,
+        receiver: PublicKey,
+        cTokenProgramId: PublicKey,
+    ): TransactionInstruction {
+        const [blockedAccount] = findBlockedAccountAddress(
+            config,
+            account,
+            cTokenProgramId,
+        );
+        const keys = [
+            {pubkey: config, isSigner: false, isWritable: false},
+            {pubkey: owner, isSigner: true, isWritable: false},
+            {pubkey: blockedAccount, isSigner: false, isWritable: true},
+            {pubkey: receiver, isSigner: false, isWritable: true},
+        ];
+
+        const data = new UnblockPayload({
+            id: InstructionVariant.Unblock,
+        });
+
+        return new TransactionInstruction({
+            keys,
+            programId: cTokenProgramId,
+            data: Buffer.from(borsh.serialize(UnblockPayloadSchema, data)),
         });
     }
 
     static async createCToken(
         connection: Connection,
-        cToken: Keypair,
-        authority: PublicKey,
         tokenMint: PublicKey,
         tokenAccount: PublicKey,
         tokenProgramId: PublicKey,
@@ -577,42 +2394,46 @@ export class CToken {
         destination: number,
         max: number | bigint,
         min: number | bigint,
+        mode: CTokenMode,
+        remoteDecimals: number,
+        recipientFormat: RecipientFormat,
         cTokenProgramId: PublicKey,
         payer: Keypair,
         confirmOptions?: ConfirmOptions,
     ): Promise<TransactionSignature> {
-        const balanceNeeded =
-            await CToken.getMinBalanceRentForExemptCToken(connection);
-        const transaction = new Transaction();
-        transaction.add(
-            SystemProgram.createAccount({
-                fromPubkey: payer.publicKey,
-                newAccountPubkey: cToken.publicKey,
-                lamports: balanceNeeded,
-                space: C_TOKEN_ACCOUNT_SIZE,
-                programId: cTokenProgramId,
-            }),
+        const [cToken] = findCTokenAddress(
+            config,
+            tokenMint,
+            destination,
+            cTokenProgramId,
+        );
+        const [authority] = PublicKey.findProgramAddressSync(
+            [cToken.toBuffer()],
+            cTokenProgramId,
         );
 
         const instruction = CToken.createCTokenInstruction(
-            cToken.publicKey,
+            cToken,
             authority,
             tokenMint,
             tokenAccount,
             tokenProgramId,
             owner.publicKey,
             config,
+            payer.publicKey,
             destination,
             max,
             min,
+            mode,
+            remoteDecimals,
+            recipientFormat,
             cTokenProgramId,
         );
 
-        transaction.add(instruction);
         return await sendAndConfirmTransaction(
             connection,
-            transaction,
-            [payer, cToken, owner],
+            new Transaction().add(instruction),
+            [payer, owner],
             confirmOptions,
         );
     }
@@ -626,12 +2447,14 @@ export class CToken {
         userTransferAuthority: Keypair,
         tokenMint: PublicKey,
         tokenProgramId: PublicKey,
+        destination: number,
         amount: bigint,
         recipient: string,
         payload: number[],
         payer: Keypair,
         cTokenProgramId: PublicKey,
         confirmOptions?: ConfirmOptions,
+        route?: PublicKey,
     ): Promise<TransactionSignature> {
         return await sendAndConfirmTransaction(
             connection,
@@ -642,12 +2465,16 @@ export class CToken {
                     tokenAccount,
                     userAccount,
                     userTransferAuthority.publicKey,
+                    userTransferAuthority.publicKey,
                     tokenMint,
                     tokenProgramId,
+                    destination,
                     amount,
                     recipient,
                     payload,
                     cTokenProgramId,
+                    undefined,
+                    route,
                 ),
             ),
             [payer, userTransferAuthority],
@@ -664,12 +2491,14 @@ export class CToken {
         userTransferAuthority: Keypair,
         tokenMint: PublicKey,
         tokenProgramId: PublicKey,
+        destination: number,
         amount: bigint,
         recipient: string,
         payload: number[],
         payer: Keypair,
         cTokenProgramId: PublicKey,
         confirmOptions?: ConfirmOptions,
+        route?: PublicKey,
     ): Promise<TransactionSignature> {
         const addPriorityFee = ComputeBudgetProgram.setComputeUnitPrice({
             microLamports: 1000,
@@ -694,12 +2523,16 @@ export class CToken {
                             tokenAccount,
                             userAccount,
                             userTransferAuthority.publicKey,
+                            payer.publicKey,
                             tokenMint,
                             tokenProgramId,
+                            destination,
                             amount,
                             recipient,
                             payload,
                             cTokenProgramId,
+                            undefined,
+                            route,
                         ),
                     ],
                 ),
@@ -716,9 +2549,11 @@ export class CToken {
         tokenAuthority: PublicKey,
         tokenAccount: PublicKey,
         userAccount: PublicKey,
+        userOwner: PublicKey,
         tokenMint: PublicKey,
         tokenProgramId: PublicKey,
         amount: bigint,
+        transferId: TransferId,
         payer: Keypair,
         cTokenProgramId: PublicKey,
         confirmOptions?: ConfirmOptions,
@@ -732,10 +2567,13 @@ export class CToken {
                     tokenAuthority,
                     tokenAccount,
                     userAccount,
+                    userOwner,
                     payer.publicKey,
                     tokenMint,
                     tokenProgramId,
+                    payer.publicKey,
                     amount,
+                    transferId,
                     cTokenProgramId,
                 ),
             ),
@@ -771,4 +2609,298 @@ export class CToken {
             confirmOptions,
         );
     }
+
+    // payer is owner
+    static async changeVolumeLimit(
+        connection: Connection,
+        cToken: PublicKey,
+        config: PublicKey,
+        window: number,
+        outbound: bigint,
+        inbound: bigint,
+        payer: Keypair,
+        cTokenProgramId: PublicKey,
+        confirmOptions?: ConfirmOptions,
+    ): Promise<TransactionSignature> {
+        return await sendAndConfirmTransaction(
+            connection,
+            new Transaction().add(
+                CToken.changeVolumeLimitInstruction(
+                    cToken,
+                    config,
+                    payer.publicKey,
+                    window,
+                    outbound,
+                    inbound,
+                    cTokenProgramId,
+                ),
+            ),
+            [payer],
+            confirmOptions,
+        );
+    }
+
+    // payer is owner
+    static async changeTokenFee(
+        connection: Connection,
+        cToken: PublicKey,
+        config: PublicKey,
+        bps: number,
+        min: bigint,
+        max: bigint,
+        feeToken: PublicKey | null,
+        payer: Keypair,
+        cTokenProgramId: PublicKey,
+        confirmOptions?: ConfirmOptions,
+    ): Promise<TransactionSignature> {
+        return await sendAndConfirmTransaction(
+            connection,
+            new Transaction().add(
+                CToken.changeTokenFeeInstruction(
+                    cToken,
+                    config,
+                    payer.publicKey,
+                    bps,
+                    min,
+                    max,
+                    feeToken,
+                    cTokenProgramId,
+                ),
+            ),
+            [payer],
+            confirmOptions,
+        );
+    }
+
+    // payer is owner and receives the rent
+    static async closeCToken(
+        connection: Connection,
+        cToken: PublicKey,
+        config: PublicKey,
+        tokenAccount: PublicKey,
+        destination: PublicKey,
+        tokenMint: PublicKey,
+        newMintAuthority: PublicKey,
+        tokenProgramId: PublicKey,
+        payer: Keypair,
+        cTokenProgramId: PublicKey,
+        confirmOptions?: ConfirmOptions,
+        feeToken?: PublicKey,
+    ): Promise<TransactionSignature> {
+        const [tokenAuthority] = PublicKey.findProgramAddressSync(
+            [cToken.toBuffer()],
+            cTokenProgramId,
+        );
+        return await sendAndConfirmTransaction(
+            connection,
+            new Transaction().add(
+                CToken.closeCTokenInstruction(
+                    cToken,
+                    config,
+                    payer.publicKey,
+                    tokenAuthority,
+                    tokenAccount,
+                    destination,
+                    tokenMint,
+                    newMintAuthority,
+                    tokenProgramId,
+                    payer.publicKey,
+                    cTokenProgramId,
+                    feeToken,
+                ),
+            ),
+            [payer],
+            confirmOptions,
+        );
+    }
+
+    // payer is owner
+    static async setLiquidityProvider(
+        connection: Connection,
+        cToken: PublicKey,
+        config: PublicKey,
+        provider: PublicKey,
+        enabled: boolean,
+        payer: Keypair,
+        cTokenProgramId: PublicKey,
+        confirmOptions?: ConfirmOptions,
+    ): Promise<TransactionSignature> {
+        return await sendAndConfirmTransaction(
+            connection,
+            new Transaction().add(
+                CToken.setLiquidityProviderInstruction(
+                    cToken,
+                    config,
+                    payer.publicKey,
+                    provider,
+                    enabled,
+                    payer.publicKey,
+                    cTokenProgramId,
+                ),
+            ),
+            [payer],
+            confirmOptions,
+        );
+    }
+
+    // payer is provider
+    static async addLiquidity(
+        connection: Connection,
+        cToken: PublicKey,
+        config: PublicKey,
+        source: PublicKey,
+        tokenAccount: PublicKey,
+        tokenMint: PublicKey,
+        tokenProgramId: PublicKey,
+        amount: bigint,
+        payer: Keypair,
+        cTokenProgramId: PublicKey,
+        confirmOptions?: ConfirmOptions,
+    ): Promise<TransactionSignature> {
+        return await sendAndConfirmTransaction(
+            connection,
+            new Transaction().add(
+                CToken.addLiquidityInstruction(
+                    cToken,
+                    config,
+                    payer.publicKey,
+                    source,
+                    tokenAccount,
+                    tokenMint,
+                    tokenProgramId,
+                    amount,
+                    cTokenProgramId,
+                ),
+            ),
+            [payer],
+            confirmOptions,
+        );
+    }
+
+    // payer is provider
+    static async removeLiquidity(
+        connection: Connection,
+        cToken: PublicKey,
+        config: PublicKey,
+        tokenAccount: PublicKey,
+        destination: PublicKey,
+        tokenMint: PublicKey,
+        tokenProgramId: PublicKey,
+        amount: bigint,
+        payer: Keypair,
+        cTokenProgramId: PublicKey,
+        confirmOptions?: ConfirmOptions,
+    ): Promise<TransactionSignature> {
+        const [tokenAuthority] = PublicKey.findProgramAddressSync(
+            [cToken.toBuffer()],
+            cTokenProgramId,
+        );
+        return await sendAndConfirmTransaction(
+            connection,
+            new Transaction().add(
+                CToken.removeLiquidityInstruction(
+                    cToken,
+                    config,
+                    payer.publicKey,
+                    tokenAuthority,
+                    tokenAccount,
+                    destination,
+                    tokenMint,
+                    tokenProgramId,
+                    amount,
+                    cTokenProgramId,
+                ),
+            ),
+            [payer],
+            confirmOptions,
+        );
+    }
+
+    // payer is owner
+    static async changeLiquidityReserve(
+        connection: Connection,
+        cToken: PublicKey,
+        config: PublicKey,
+        reserve: bigint,
+        payer: Keypair,
+        cTokenProgramId: PublicKey,
+        confirmOptions?: ConfirmOptions,
+    ): Promise<TransactionSignature> {
+        return await sendAndConfirmTransaction(
+            connection,
+            new Transaction().add(
+                CToken.changeLiquidityReserveInstruction(
+                    cToken,
+                    config,
+                    payer.publicKey,
+                    reserve,
+                    cTokenProgramId,
+                ),
+            ),
+            [payer],
+            confirmOptions,
+        );
+    }
+
+    // payer is owner
+    static async addRoute(
+        connection: Connection,
+        cToken: PublicKey,
+        config: PublicKey,
+        destination: number,
+        max: bigint,
+        min: bigint,
+        fee: bigint,
+        recipientFormat: RecipientFormat,
+        payer: Keypair,
+        cTokenProgramId: PublicKey,
+        confirmOptions?: ConfirmOptions,
+    ): Promise<TransactionSignature> {
+        return await sendAndConfirmTransaction(
+            connection,
+            new Transaction().add(
+                CToken.addRouteInstruction(
+                    cToken,
+                    config,
+                    payer.publicKey,
+                    destination,
+                    max,
+                    min,
+                    fee,
+                    recipientFormat,
+                    payer.publicKey,
+                    cTokenProgramId,
+                ),
+            ),
+            [payer],
+            confirmOptions,
+        );
+    }
+
+    // payer is owner and receives the rent
+    static async removeRoute(
+        connection: Connection,
+        cToken: PublicKey,
+        config: PublicKey,
+        destination: number,
+        payer: Keypair,
+        cTokenProgramId: PublicKey,
+        confirmOptions?: ConfirmOptions,
+    ): Promise<TransactionSignature> {
+        return await sendAndConfirmTransaction(
+            connection,
+            new Transaction().add(
+                CToken.removeRouteInstruction(
+                    cToken,
+                    config,
+                    payer.publicKey,
+                    destination,
+                    payer.publicKey,
+                    cTokenProgramId,
+                ),
+            ),
+            [payer],
+            confirmOptions,
+        );
+    }
 }
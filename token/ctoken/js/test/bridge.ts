@@ -7,7 +7,12 @@ import {
     TOKEN_2022_PROGRAM_ID,
 } from '@solana/spl-token';
 import * as borsh from 'borsh';
-import {CToken, cTokenAccount, cTokenAccountSchema} from '../src';
+import {
+    CToken,
+    cTokenAccount,
+    cTokenAccountSchema,
+    findRouteAddress,
+} from '../src';
 
 async function main() {
     // const rpc = clusterApiUrl('mainnet-beta');
@@ -61,6 +66,13 @@ async function main() {
     // );
 
     const recipient = '0xBD62fB256F6F6a91B6F14716eA538FD2973E5c3b';
+    // @ts-ignore
+    const cTokenDestination = cTokenAccountState.destination as number;
+    const destination = Number(process.env.DESTINATION ?? cTokenDestination);
+    const route =
+        destination === cTokenDestination
+            ? undefined
+            : findRouteAddress(cToken, destination, cTokenProgramId)[0];
 
     const signature = await CToken.approveBridge(
         connection,
@@ -71,11 +83,14 @@ async function main() {
         userTransferAuthority,
         tokenMint,
         tokenProgramId,
+        destination,
         amount,
         recipient,
         [], // payload
         payer,
         cTokenProgramId,
+        undefined,
+        route,
     );
     console.log(`Bridge tx ${signature.toString()}`);
 }
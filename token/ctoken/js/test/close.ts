@@ -0,0 +1,58 @@
+import * as fs from 'fs';
+import {PublicKey, Keypair, Connection} from '@solana/web3.js';
+import * as borsh from 'borsh';
+import {CToken, cTokenAccount, cTokenAccountSchema} from '../src';
+
+async function main() {
+    // const rpc = clusterApiUrl('devnet');
+    const rpc = `${process.env.SOLANA_RPC_URL}`;
+
+    const secret = JSON.parse(
+        fs.readFileSync(`${process.env.PRIVATE_KEY_PATH}`).toString(),
+    ) as number[];
+    const secretKey = Uint8Array.from(secret);
+    const payer = Keypair.fromSecretKey(secretKey);
+    const connection = new Connection(rpc, 'confirmed');
+
+    const cTokenProgramId = new PublicKey(`${process.env.C_TOKEN_PROGRAM_ID}`);
+    const cToken = new PublicKey(`${process.env.C_TOKEN}`);
+    const config = new PublicKey(`${process.env.CONFIG}`);
+
+    const cTokenData = await connection.getAccountInfo(cToken, 'confirmed');
+    const cTokenAccountState = borsh.deserialize(
+        cTokenAccountSchema,
+        cTokenAccount,
+        cTokenData!.data,
+    );
+    // @ts-ignore
+    const tokenAccount = new PublicKey(cTokenAccountState.token);
+    // @ts-ignore
+    const tokenMint = new PublicKey(cTokenAccountState.token_mint);
+    // @ts-ignore
+    const tokenProgramId = new PublicKey(cTokenAccountState.token_program_id);
+
+    // token account receiving the vault balance of a lock cToken
+    const destination = new PublicKey(
+        `${process.env.DESTINATION ?? tokenAccount.toBase58()}`,
+    );
+    // new mint authority of a mint cToken
+    const newMintAuthority = new PublicKey(
+        `${process.env.NEW_MINT_AUTHORITY ?? payer.publicKey.toBase58()}`,
+    );
+
+    const signature = await CToken.closeCToken(
+        connection,
+        cToken,
+        config,
+        tokenAccount,
+        destination,
+        tokenMint,
+        newMintAuthority,
+        tokenProgramId,
+        payer,
+        cTokenProgramId,
+    );
+    console.log(`Close cToken ${cToken.toBase58()} tx ${signature.toString()}`);
+}
+
+main();
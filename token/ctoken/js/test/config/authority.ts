@@ -24,7 +24,7 @@ async function main() {
         cTokenProgramId,
         payer,
     );
-    console.log(`Change authority to ${process.env.AUTHORITY}`);
+    console.log(`Propose authority ${process.env.AUTHORITY}`);
 }
 
 main();
@@ -0,0 +1,30 @@
+import * as fs from 'fs';
+import {PublicKey, Keypair, Connection} from '@solana/web3.js';
+import {Config} from '../../src';
+
+async function main() {
+    // const rpc = clusterApiUrl('devnet');
+    const rpc = `${process.env.SOLANA_RPC_URL}`;
+
+    const secret = JSON.parse(
+        fs.readFileSync(`${process.env.PRIVATE_KEY_PATH}`).toString(),
+    ) as number[];
+    const secretKey = Uint8Array.from(secret);
+    const payer = Keypair.fromSecretKey(secretKey);
+    const connection = new Connection(rpc, 'confirmed');
+
+    const config = new PublicKey(`${process.env.CONFIG}`);
+    const cTokenProgramId = new PublicKey(`${process.env.C_TOKEN_PROGRAM_ID}`);
+    const authority = process.env.AUTHORITY !== undefined;
+
+    await Config.cancelPending(
+        connection,
+        config,
+        authority,
+        cTokenProgramId,
+        payer,
+    );
+    console.log(`Cancel pending ${authority ? 'authority' : 'owner'}`);
+}
+
+main();
@@ -16,22 +16,15 @@ async function main() {
 
     const seed = process.env.SEED!;
     const cTokenProgramId = new PublicKey(`${process.env.C_TOKEN_PROGRAM_ID}`);
-    const configPubkey = await PublicKey.createWithSeed(
-        payer.publicKey,
-        seed,
-        cTokenProgramId,
-    );
 
     console.log(`creating cToken config`);
-    await Config.initialConfig(
+    const [configPubkey] = await Config.initialConfig(
         connection,
-        configPubkey,
         seed,
-        payer.publicKey, // owner
         authority, // authority
         0, // fee
         payer.publicKey, // fee collector,
-        payer,
+        payer, // owner
         cTokenProgramId,
     );
     console.log(`Created cToken config is ${configPubkey}`);
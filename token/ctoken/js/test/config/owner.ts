@@ -24,7 +24,7 @@ async function main() {
         cTokenProgramId,
         payer,
     );
-    console.log(`Transfer onwer to ${process.env.NEW_OWNER}`);
+    console.log(`Propose owner ${process.env.NEW_OWNER}`);
 }
 
 main();
@@ -11,13 +11,16 @@ async function main() {
     const config = new PublicKey(`${process.env.CONFIG}`);
 
     const configData = await connection.getAccountInfo(config, 'confirmed');
-    const configState = borsh.deserialize(
+    // pending changes may leave trailing space
+    const configState = borsh.deserializeUnchecked(
         cTokenConfigSchema,
         cTokenConfig,
         configData!.data,
     );
     // @ts-ignore
-    const is_initialized = configState.initialized;
+    const account_type = configState.account_type;
+    // @ts-ignore
+    const version = configState.version;
     // @ts-ignore
     const owner = new PublicKey(configState.owner);
     // @ts-ignore
@@ -26,14 +29,30 @@ async function main() {
     const fee = configState.fee;
     // @ts-ignore
     const fee_collector = new PublicKey(configState.fee_collector);
+    // @ts-ignore
+    const paused = configState.paused;
+    // @ts-ignore
+    const pending_owner = configState.pending_owner
+        ? // @ts-ignore
+          new PublicKey(configState.pending_owner)
+        : null;
+    // @ts-ignore
+    const pending_authority = configState.pending_authority
+        ? // @ts-ignore
+          new PublicKey(configState.pending_authority)
+        : null;
 
     console.log(`Config ${process.env.CONFIG}:
     {
-        is_initialized: ${is_initialized},
+        account_type: ${account_type},
+        version: ${version},
         owner: ${owner},
         authority: ${authority},
         fee: ${fee},
-        fee_collector: ${fee_collector}
+        fee_collector: ${fee_collector},
+        paused: ${paused},
+        pending_owner: ${pending_owner},
+        pending_authority: ${pending_authority}
     }`);
 }
 
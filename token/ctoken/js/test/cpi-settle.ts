@@ -7,10 +7,17 @@ import {
     TransactionInstruction,
     sendAndConfirmTransaction,
     Transaction,
+    SystemProgram,
 } from '@solana/web3.js';
 import {getAssociatedTokenAddress, TOKEN_PROGRAM_ID} from '@solana/spl-token';
 import * as borsh from 'borsh';
-import {cTokenAccount, cTokenAccountSchema} from '../src';
+import {
+    cTokenAccount,
+    cTokenAccountSchema,
+    findSettlementReceiptAddress,
+    TransferId,
+    TransferIdSchema,
+} from '../src';
 
 async function main() {
     const secret = JSON.parse(
@@ -52,10 +59,25 @@ async function main() {
 
     console.log(`authority: ${helloPDAPubkey.toString()}`);
 
+    const transferId = new TransferId({
+        // @ts-ignore
+        source_chain: cTokenAccountState.destination,
+        cashier: Buffer.from(
+            `${process.env.CASHIER}`.replace(/^0x/, ''),
+            'hex',
+        ),
+        index: BigInt(`${process.env.INDEX}`),
+    });
+    const [receipt] = findSettlementReceiptAddress(
+        config,
+        transferId,
+        cTokenProgramId,
+    );
+
     const instruction = new TransactionInstruction({
         keys: [
             {pubkey: cTokenProgramId, isSigner: false, isWritable: false},
-            {pubkey: cToken, isSigner: false, isWritable: false},
+            {pubkey: cToken, isSigner: false, isWritable: true},
             {pubkey: authority, isSigner: false, isWritable: false},
             {pubkey: tokenAccount, isSigner: false, isWritable: false},
             {pubkey: userInfo, isSigner: false, isWritable: true},
@@ -63,9 +85,16 @@ async function main() {
             {pubkey: tokenMint, isSigner: false, isWritable: true},
             {pubkey: config, isSigner: false, isWritable: false},
             {pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false},
+            {pubkey: receipt, isSigner: false, isWritable: true},
+            {pubkey: payer.publicKey, isSigner: true, isWritable: true},
+            {
+                pubkey: SystemProgram.programId,
+                isSigner: false,
+                isWritable: false,
+            },
         ],
         programId: helloProgramId,
-        data: Buffer.alloc(0),
+        data: Buffer.from(borsh.serialize(TransferIdSchema, transferId)),
     });
     const signature = await sendAndConfirmTransaction(
         connection,
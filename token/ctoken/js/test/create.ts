@@ -39,7 +39,14 @@ import {
     signerIdentity,
 } from '@metaplex-foundation/umi';
 import {createUmi} from '@metaplex-foundation/umi-bundle-defaults';
-import {CToken} from '../src';
+import {
+    CToken,
+    CTokenMode,
+    NATIVE_MINT,
+    RecipientFormat,
+    findCTokenAddress,
+    recipientFormatOf,
+} from '../src';
 
 function addMetadataInstructions(
     payer: Keypair,
@@ -94,24 +101,52 @@ async function main() {
     const connection = new Connection(rpc, 'confirmed');
 
     const cTokenProgramId = new PublicKey(`${process.env.C_TOKEN_PROGRAM_ID}`);
+    const config = new PublicKey(`${process.env.CONFIG}`);
+
+    const destination = Number(process.env.DESTINATION ?? 4689);
+    const recipientFormat =
+        process.env.RECIPIENT_FORMAT === 'iotex'
+            ? RecipientFormat.IoTeX
+            : process.env.RECIPIENT_FORMAT === 'evm'
+              ? RecipientFormat.Evm
+              : recipientFormatOf(destination);
+    if (recipientFormat === undefined) {
+        throw new Error(`RECIPIENT_FORMAT is required for ${destination}`);
+    }
+    // mint when the base chain is not solana, lock otherwise, native for SOL
+    const mode =
+        process.env.MODE === 'lock'
+            ? CTokenMode.Lock
+            : process.env.MODE === 'native'
+              ? CTokenMode.Native
+              : CTokenMode.Mint;
     // TODO hardcode for now
-    const tokenProgramId = TOKEN_PROGRAM_ID;
-    const cTokenAccount = Keypair.generate();
+    const tokenProgramId =
+        mode === CTokenMode.Native
+            ? SystemProgram.programId
+            : TOKEN_PROGRAM_ID;
+    const tokenKeypair = Keypair.generate();
+    const tokenMint =
+        mode === CTokenMode.Mint
+            ? tokenKeypair.publicKey
+            : mode === CTokenMode.Native
+              ? NATIVE_MINT
+              : new PublicKey(`${process.env.TOKEN_MINT}`);
+    const [cToken] = findCTokenAddress(
+        config,
+        tokenMint,
+        destination,
+        cTokenProgramId,
+    );
     const [authority, _bumpSeed] = PublicKey.findProgramAddressSync(
-        [cTokenAccount.publicKey.toBuffer()],
+        [cToken.toBuffer()],
         cTokenProgramId,
     );
-    const config = new PublicKey(`${process.env.CONFIG}`);
-
-    const destination = 0;
-    let tokenMint;
     let tokenAccount;
 
-    if (destination === 0) {
+    if (mode === CTokenMode.Mint) {
         // base chain is not solana
         console.log('creating token mint');
-        const tokenKeypair = Keypair.generate();
-        tokenMint = tokenKeypair.publicKey;
         const airdropOwner = new PublicKey("HUhE58jBjbPYRwXTTX9SxLKDqKe14EmkDRAvqCMGHvae");
         const airdropATA = await getAssociatedTokenAddress(
             tokenMint,
@@ -174,10 +209,11 @@ async function main() {
         );
 
         tokenAccount = tokenMint;
+    } else if (mode === CTokenMode.Native) {
+        // SOL is locked in the authority
+        tokenAccount = authority;
     } else {
         // base chain is solana
-        tokenMint = new PublicKey(`${process.env.TOKEN_MINT}`);
-
         console.log(`creating token account`);
         tokenAccount = await createAccount(
             connection,
@@ -190,7 +226,6 @@ async function main() {
         );
     }
 
-    console.log(`cTokenAccount: ${JSON.stringify(cTokenAccount.secretKey)}`);
     console.log(`authority: ${authority.toBase58()}`);
     console.log(`tokenMint: ${tokenMint.toBase58()}`);
     console.log(`tokenAccount: ${tokenAccount.toBase58()}`);
@@ -198,8 +233,6 @@ async function main() {
     console.log(`creating cToken account`);
     await CToken.createCToken(
         connection,
-        cTokenAccount,
-        authority,
         tokenMint,
         tokenAccount,
         tokenProgramId,
@@ -208,11 +241,14 @@ async function main() {
         destination,
         10000000000000000n,
         1000000000,
+        mode,
+        Number(process.env.REMOTE_DECIMALS ?? 18),
+        recipientFormat,
         cTokenProgramId,
         payer,
     );
     console.log(
-        `Solana cToken for ${tokenMint} token with ${authority} authority and ${destination} destination is ${cTokenAccount.publicKey}`,
+        `Solana cToken for ${tokenMint} token with ${authority} authority, ${destination} destination and ${CTokenMode[mode]} mode is ${cToken}`,
     );
 }
 
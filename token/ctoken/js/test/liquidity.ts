@@ -0,0 +1,98 @@
+import * as fs from 'fs';
+import {PublicKey, Keypair, Connection} from '@solana/web3.js';
+import {getAssociatedTokenAddress} from '@solana/spl-token';
+import * as borsh from 'borsh';
+import {CToken, cTokenAccount, cTokenAccountSchema} from '../src';
+
+async function main() {
+    // const rpc = clusterApiUrl('devnet');
+    const rpc = `${process.env.SOLANA_RPC_URL}`;
+
+    const secret = JSON.parse(
+        fs.readFileSync(`${process.env.PRIVATE_KEY_PATH}`).toString(),
+    ) as number[];
+    const secretKey = Uint8Array.from(secret);
+    const payer = Keypair.fromSecretKey(secretKey);
+    const connection = new Connection(rpc, 'confirmed');
+
+    const cTokenProgramId = new PublicKey(`${process.env.C_TOKEN_PROGRAM_ID}`);
+    const cToken = new PublicKey(`${process.env.C_TOKEN}`);
+    const config = new PublicKey(`${process.env.CONFIG}`);
+
+    const cTokenData = await connection.getAccountInfo(cToken, 'confirmed');
+    const cTokenAccountState = borsh.deserialize(
+        cTokenAccountSchema,
+        cTokenAccount,
+        cTokenData!.data,
+    );
+    // @ts-ignore
+    const tokenAccount = new PublicKey(cTokenAccountState.token);
+    // @ts-ignore
+    const tokenMint = new PublicKey(cTokenAccountState.token_mint);
+    // @ts-ignore
+    const tokenProgramId = new PublicKey(cTokenAccountState.token_program_id);
+    const userToken = await getAssociatedTokenAddress(
+        tokenMint,
+        payer.publicKey,
+        false,
+        tokenProgramId,
+    );
+    const amount = BigInt(`${process.env.AMOUNT}`);
+
+    let signature;
+    switch (process.env.ACTION) {
+        case 'whitelist':
+            // payer is owner
+            signature = await CToken.setLiquidityProvider(
+                connection,
+                cToken,
+                config,
+                new PublicKey(`${process.env.PROVIDER}`),
+                process.env.ENABLED !== 'false',
+                payer,
+                cTokenProgramId,
+            );
+            break;
+        case 'reserve':
+            // payer is owner
+            signature = await CToken.changeLiquidityReserve(
+                connection,
+                cToken,
+                config,
+                amount,
+                payer,
+                cTokenProgramId,
+            );
+            break;
+        case 'remove':
+            signature = await CToken.removeLiquidity(
+                connection,
+                cToken,
+                config,
+                tokenAccount,
+                userToken,
+                tokenMint,
+                tokenProgramId,
+                amount,
+                payer,
+                cTokenProgramId,
+            );
+            break;
+        default:
+            signature = await CToken.addLiquidity(
+                connection,
+                cToken,
+                config,
+                userToken,
+                tokenAccount,
+                tokenMint,
+                tokenProgramId,
+                amount,
+                payer,
+                cTokenProgramId,
+            );
+    }
+    console.log(`Liquidity tx ${signature.toString()}`);
+}
+
+main();
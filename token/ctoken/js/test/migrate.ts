@@ -0,0 +1,42 @@
+import * as fs from 'fs';
+import {PublicKey, Keypair, Connection} from '@solana/web3.js';
+import {Config} from '../src';
+
+async function main() {
+    // const rpc = clusterApiUrl('devnet');
+    const rpc = `${process.env.SOLANA_RPC_URL}`;
+
+    const secret = JSON.parse(
+        fs.readFileSync(`${process.env.PRIVATE_KEY_PATH}`).toString(),
+    ) as number[];
+    const secretKey = Uint8Array.from(secret);
+    const payer = Keypair.fromSecretKey(secretKey);
+    const connection = new Connection(rpc, 'confirmed');
+
+    const config = new PublicKey(`${process.env.CONFIG}`);
+    const cTokenProgramId = new PublicKey(`${process.env.C_TOKEN_PROGRAM_ID}`);
+    // comma separated cToken:tokenMint pairs under config
+    const cTokens = `${process.env.C_TOKENS ?? ''}`
+        .split(',')
+        .filter(pair => pair !== '')
+        .map(pair => {
+            const [cToken, tokenMint] = pair.split(':');
+            return [new PublicKey(cToken), new PublicKey(tokenMint)] as [
+                PublicKey,
+                PublicKey,
+            ];
+        });
+    const remoteDecimals = Number(process.env.REMOTE_DECIMALS ?? 18);
+
+    const signature = await Config.migrate(
+        connection,
+        config,
+        cTokens,
+        remoteDecimals,
+        cTokenProgramId,
+        payer, // owner
+    );
+    console.log(`Migrate ${config} and ${cTokens.length} cTokens tx ${signature}`);
+}
+
+main();
@@ -0,0 +1,37 @@
+import * as fs from 'fs';
+import {PublicKey, Keypair, Connection} from '@solana/web3.js';
+import {Config} from '../src';
+
+async function main() {
+    // const rpc = clusterApiUrl('devnet');
+    const rpc = `${process.env.SOLANA_RPC_URL}`;
+
+    const secret = JSON.parse(
+        fs.readFileSync(`${process.env.PRIVATE_KEY_PATH}`).toString(),
+    ) as number[];
+    const secretKey = Uint8Array.from(secret);
+    const payer = Keypair.fromSecretKey(secretKey);
+    const connection = new Connection(rpc, 'confirmed');
+
+    const config = new PublicKey(`${process.env.CONFIG}`);
+    const cTokenProgramId = new PublicKey(`${process.env.C_TOKEN_PROGRAM_ID}`);
+    // pause the whole config if C_TOKEN is not set
+    const cToken = process.env.C_TOKEN
+        ? new PublicKey(process.env.C_TOKEN)
+        : null;
+    const paused = process.env.UNPAUSE === undefined;
+
+    await Config.setPaused(
+        connection,
+        config,
+        cToken,
+        paused,
+        cTokenProgramId,
+        payer, // owner
+    );
+    console.log(
+        `${paused ? 'Paused' : 'Unpaused'} ${cToken ? cToken : config}`,
+    );
+}
+
+main();
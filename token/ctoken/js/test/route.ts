@@ -0,0 +1,59 @@
+import * as fs from 'fs';
+import {PublicKey, Keypair, Connection} from '@solana/web3.js';
+import {CToken, RecipientFormat, recipientFormatOf} from '../src';
+
+async function main() {
+    // const rpc = clusterApiUrl('devnet');
+    const rpc = `${process.env.SOLANA_RPC_URL}`;
+
+    const secret = JSON.parse(
+        fs.readFileSync(`${process.env.PRIVATE_KEY_PATH}`).toString(),
+    ) as number[];
+    const secretKey = Uint8Array.from(secret);
+    const payer = Keypair.fromSecretKey(secretKey);
+    const connection = new Connection(rpc, 'confirmed');
+
+    const cTokenProgramId = new PublicKey(`${process.env.C_TOKEN_PROGRAM_ID}`);
+    const cToken = new PublicKey(`${process.env.C_TOKEN}`);
+    const config = new PublicKey(`${process.env.CONFIG}`);
+    const destination = Number(process.env.DESTINATION);
+    const recipientFormat =
+        process.env.RECIPIENT_FORMAT === 'iotex'
+            ? RecipientFormat.IoTeX
+            : process.env.RECIPIENT_FORMAT === 'evm'
+              ? RecipientFormat.Evm
+              : recipientFormatOf(destination);
+
+    let signature;
+    if (process.env.REMOVE === 'true') {
+        signature = await CToken.removeRoute(
+            connection,
+            cToken,
+            config,
+            destination,
+            payer,
+            cTokenProgramId,
+        );
+    } else {
+        if (recipientFormat === undefined) {
+            throw new Error(`RECIPIENT_FORMAT is required for ${destination}`);
+        }
+        signature = await CToken.addRoute(
+            connection,
+            cToken,
+            config,
+            destination,
+            BigInt(`${process.env.MAX}`),
+            BigInt(`${process.env.MIN}`),
+            BigInt(process.env.FEE ?? 0), // lamports
+            recipientFormat,
+            payer,
+            cTokenProgramId,
+        );
+    }
+    console.log(
+        `Route of ${cToken.toBase58()} to ${destination} tx ${signature.toString()}`,
+    );
+}
+
+main();
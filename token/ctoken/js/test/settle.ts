@@ -2,7 +2,12 @@ import * as fs from 'fs';
 import {PublicKey, Keypair, Connection, clusterApiUrl} from '@solana/web3.js';
 import {getAssociatedTokenAddress, TOKEN_PROGRAM_ID} from '@solana/spl-token';
 import * as borsh from 'borsh';
-import {CToken, cTokenAccount, cTokenAccountSchema} from '../src';
+import {
+    CToken,
+    cTokenAccount,
+    cTokenAccountSchema,
+    TransferId,
+} from '../src';
 
 async function main() {
     // const rpc = clusterApiUrl('mainnet-beta');
@@ -42,6 +47,15 @@ async function main() {
     const tokenAccount = new PublicKey(cTokenAccountState.token);
 
     const amount = 1000000000n;
+    const transferId = new TransferId({
+        // @ts-ignore
+        source_chain: cTokenAccountState.destination,
+        cashier: Buffer.from(
+            `${process.env.CASHIER}`.replace(/^0x/, ''),
+            'hex',
+        ),
+        index: BigInt(`${process.env.INDEX}`),
+    });
 
     const signature = await CToken.settle(
         connection,
@@ -50,9 +64,11 @@ async function main() {
         tokenAuthority,
         tokenAccount,
         userInfo,
+        payer.publicKey,
         tokenMint,
         TOKEN_PROGRAM_ID,
         amount,
+        transferId,
         payer,
         cTokenProgramId,
     );
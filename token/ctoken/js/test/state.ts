@@ -1,6 +1,6 @@
 import {PublicKey, Connection, clusterApiUrl} from '@solana/web3.js';
 import * as borsh from 'borsh';
-import {cTokenAccount, cTokenAccountSchema} from '../src';
+import {CTokenMode, cTokenAccount, cTokenAccountSchema} from '../src';
 
 async function main() {
     // const rpc = clusterApiUrl('devnet');
@@ -21,7 +21,9 @@ async function main() {
         cTokenData!.data,
     );
     // @ts-ignore
-    const is_initialized = cTokenAccountState.initialized;
+    const account_type = cTokenAccountState.account_type;
+    // @ts-ignore
+    const version = cTokenAccountState.version;
     // @ts-ignore
     const bumpSeed = cTokenAccountState.bump_seed;
     // @ts-ignore
@@ -40,10 +42,13 @@ async function main() {
     const max = cTokenAccountState.max;
     // @ts-ignore
     const min = cTokenAccountState.min;
+    // @ts-ignore
+    const mode = CTokenMode[cTokenAccountState.mode];
 
     console.log(`cToken ${process.env.C_TOKEN}:
     {
-        is_initialized: ${is_initialized},
+        account_type: ${account_type},
+        version: ${version},
         bump_seed: ${bumpSeed},
         token_program_id: ${tokenProgramId},
         config: ${config},
@@ -52,7 +57,8 @@ async function main() {
         destination: ${destination},
         index: ${index},
         max: ${max},
-        min: ${min}
+        min: ${min},
+        mode: ${mode}
     }`);
 }
 
@@ -53,6 +53,54 @@ pub enum CTokenError {
     /// Invalid Amount
     #[error("Invalid amount")]
     InvalidAmount,
+    /// Paused
+    #[error("cToken is paused")]
+    Paused,
+    /// Volume limit exceeded
+    #[error("Volume limit exceeded")]
+    VolumeLimitExceeded,
+    /// Already settled
+    #[error("Transfer already settled")]
+    AlreadySettled,
+    /// Invalid account type
+    #[error("Invalid account type")]
+    InvalidAccountType,
+    /// Account not migrated
+    #[error("Account must be migrated to the current layout")]
+    AccountNotMigrated,
+    /// Liquidity provider not allowed
+    #[error("Liquidity provider is not allowed")]
+    LiquidityProviderNotAllowed,
+    /// Insufficient liquidity
+    #[error("Insufficient liquidity")]
+    InsufficientLiquidity,
+    /// Invalid recipient
+    #[error("Invalid recipient address for destination")]
+    InvalidRecipient,
+    /// Invalid route
+    #[error("Invalid destination route")]
+    InvalidRoute,
+    /// Unsupported mint
+    #[error("Mint has an unsupported extension or authority")]
+    UnsupportedMint,
+    /// Memo program required
+    #[error("Memo program account required by the destination")]
+    MemoProgramRequired,
+    /// Blocked account
+    #[error("Account is blocked")]
+    Blocked,
+    /// Invalid receiver program
+    #[error("Receiver is not an executable program owning the recipient")]
+    InvalidReceiver,
+    /// Bridge receipt not old enough to close
+    #[error("Bridge receipt can't be closed before the receipt age")]
+    ReceiptTooRecent,
+    /// Liquidity, token fees or bridge receipts left in the cToken
+    #[error("cToken still holds provided liquidity, token fees or bridge receipts")]
+    NotEmpty,
+    /// Unknown destination
+    #[error("Destination has no known recipient format")]
+    UnknownDestination,
 }
 impl From<CTokenError> for ProgramError {
     fn from(e: CTokenError) -> Self {
@@ -113,6 +161,52 @@ impl PrintProgramError for CTokenError {
             CTokenError::InvalidAmount => {
                 msg!("Error: Invalid amount")
             }
+            CTokenError::Paused => {
+                msg!("Error: cToken is paused")
+            }
+            CTokenError::VolumeLimitExceeded => {
+                msg!("Error: Volume limit exceeded")
+            }
+            CTokenError::AlreadySettled => {
+                msg!("Error: Transfer already settled")
+            }
+            CTokenError::InvalidAccountType => {
+                msg!("Error: Invalid account type")
+            }
+            CTokenError::AccountNotMigrated => {
+                msg!("Error: Account must be migrated to the current layout")
+            }
+            CTokenError::LiquidityProviderNotAllowed => {
+                msg!("Error: Liquidity provider is not allowed")
+            }
+            CTokenError::InsufficientLiquidity => {
+                msg!("Error: Insufficient liquidity")
+            }
+            CTokenError::InvalidRecipient => {
+                msg!("Error: Invalid recipient address for destination")
+            }
+            CTokenError::InvalidRoute => {
+                msg!("Error: Invalid destination route")
+            }
+            CTokenError::UnsupportedMint => {
+                msg!("Error: Mint has an unsupported extension or authority")
+            }
+            CTokenError::MemoProgramRequired => {
+                msg!("Error: Memo program account required by the destination")
+            }
+            CTokenError::Blocked => msg!("Error: Account is blocked"),
+            CTokenError::InvalidReceiver => {
+                msg!("Error: Receiver is not an executable program owning the recipient")
+            }
+            CTokenError::ReceiptTooRecent => {
+                msg!("Error: Bridge receipt can't be closed before the receipt age")
+            }
+            CTokenError::NotEmpty => {
+                msg!("Error: cToken still holds provided liquidity, token fees or bridge receipts")
+            }
+            CTokenError::UnknownDestination => {
+                msg!("Error: Destination has no known recipient format")
+            }
         }
     }
 }
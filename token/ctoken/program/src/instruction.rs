@@ -8,13 +8,27 @@ use solana_program::{
     system_program,
 };
 
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+use crate::{
+    receiver::find_caller_address,
+    recipient::RecipientFormat,
+    state::{
+        BlockedAccount, BridgeReceipt, CToken, CTokenMode, Config, LiquidityProvider, Route,
+        SettlementReceipt, TransferId,
+    },
+};
+
 /// Instructions for CToken
 #[derive(Clone, Debug, BorshSchema, BorshDeserialize, BorshSerialize, PartialEq)]
 pub enum CTokenInstruction {
     Config {
         fee: u64,
+        seed: String,
     },
+    /// Proposes a new owner, which takes effect once accepted
     TransferOwner,
+    /// Proposes a new authority, which takes effect once accepted
     ChangeAuthority,
     ChangeFee {
         fee: u64,
@@ -23,37 +37,435 @@ pub enum CTokenInstruction {
         destination: u32,
         max: u64,
         min: u64,
+        mode: CTokenMode,
+        remote_decimals: u8,
+        recipient_format: RecipientFormat,
     },
     ChangeLimit {
         max: u64,
         min: u64,
     },
+    /// Bridges to `destination`, the cToken destination or one of its routes
     Bridge {
+        destination: u32,
         amount: u64,
         recipient: String,
         payload: Vec<u8>,
     },
+    /// Settles `amount` in remote decimals
     Settle {
+        amount: u128,
+        transfer_id: TransferId,
+    },
+    /// Pauses the config, or the cToken or its route when given
+    Pause,
+    /// Unpauses the config, or the cToken or its route when given
+    Unpause,
+    ChangeVolumeLimit {
+        window: i64,
+        outbound: u64,
+        inbound: u64,
+    },
+    ChangeTokenFee {
+        bps: u16,
+        min: u64,
+        max: u64,
+    },
+    WithdrawTokenFee {
+        amount: u64,
+    },
+    AcceptOwner,
+    AcceptAuthority,
+    CancelPendingOwner,
+    CancelPendingAuthority,
+    /// Rewrites legacy config and cToken accounts in the current layout.
+    /// cTokens without decimals read them from their mint and take
+    /// `remote_decimals`
+    Migrate {
+        remote_decimals: u8,
+    },
+    /// Moves the vault balance out or hands over the mint authority, then closes the cToken.
+    /// Provided liquidity and token fees left in the fee token account must be
+    /// withdrawn and bridge receipts closed first, and routes should be
+    /// removed. A lock vault is closed along with the cToken
+    CloseCToken,
+    /// Whitelists or delists a liquidity provider of a lock cToken
+    SetLiquidityProvider {
+        enabled: bool,
+    },
+    /// Deposits provider tokens into the lock cToken vault
+    AddLiquidity {
+        amount: u64,
+    },
+    /// Withdraws provided tokens from the lock cToken vault
+    RemoveLiquidity {
+        amount: u64,
+    },
+    /// Sets the vault balance liquidity removals must leave
+    ChangeLiquidityReserve {
+        reserve: u64,
+    },
+    /// Adds a destination route to a cToken, or updates it
+    AddRoute {
+        destination: u32,
+        max: u64,
+        min: u64,
+        fee: u64,
+        recipient_format: RecipientFormat,
+    },
+    /// Removes a destination route from a cToken
+    RemoveRoute,
+    /// Sets the remote decimals of a cToken and rereads the mint decimals.
+    /// Transfers in flight are scaled with the new decimals
+    ChangeDecimals {
+        remote_decimals: u8,
+    },
+    /// Blocks an account from bridging and receiving settlements
+    Block {
+        account: Pubkey,
+    },
+    /// Removes an account from the blocklist
+    Unblock,
+    /// Settles `amount` in remote decimals like `Settle`, then invokes the
+    /// receiver program with the sender and payload of the source transfer
+    SettleAndCall {
+        amount: u128,
+        transfer_id: TransferId,
+        sender: Vec<u8>,
+        payload: Vec<u8>,
+    },
+    /// Sets the age after which bridge receipts of a cToken can be closed,
+    /// 0 to stop writing receipts
+    ChangeReceiptAge {
+        age: i64,
+    },
+    /// Closes a bridge receipt older than the receipt age of its cToken,
+    /// refunding the rent to its payer
+    CloseBridgeReceipt,
+    /// Settles several transfers of a cToken, each to one of the recipient
+    /// accounts, in one instruction
+    SettleBatch {
+        entries: Vec<SettleEntry>,
+    },
+    /// Moves lamports out of the rent vault of the config
+    WithdrawRentVault {
+        amount: u64,
+    },
+    /// Sets the lamports settle pays to recipient wallets holding less than
+    /// `threshold`, 0 for no drop-off
+    ChangeDropOff {
         amount: u64,
+        threshold: u64,
     },
+    /// Moves lamports of the owner into the drop-off vault of the config
+    FundDropOffVault {
+        amount: u64,
+    },
+    /// Moves lamports out of the drop-off vault of the config
+    WithdrawDropOffVault {
+        amount: u64,
+    },
+}
+
+/// Transfer settled by `SettleBatch`
+#[derive(Clone, Debug, BorshSchema, BorshDeserialize, BorshSerialize, PartialEq)]
+pub struct SettleEntry {
+    /// Index of the recipient account in the instruction recipients
+    pub recipient_index: u8,
+
+    /// Amount in remote decimals
+    pub amount: u128,
+
+    /// Source transfer
+    pub transfer_id: TransferId,
+}
+
+/// Creates a `Config` instruction for the config at the address of `owner` and `seed`
+pub fn config(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    authority: &Pubkey,
+    fee_collector: &Pubkey,
+    payer: &Pubkey,
+    fee: u64,
+    seed: String,
+) -> Result<Instruction, ProgramError> {
+    let (config, _) = Config::find_address(program_id, owner, &seed);
+    let data = CTokenInstruction::Config { fee, seed }.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new(config, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(*authority, false),
+        AccountMeta::new_readonly(*fee_collector, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `TransferOwner` instruction proposing `new_owner`
+pub fn transfer_owner(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    owner: &Pubkey,
+    new_owner: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = CTokenInstruction::TransferOwner.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new(*config, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(*new_owner, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `ChangeAuthority` instruction proposing `new_authority`
+pub fn change_authority(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    owner: &Pubkey,
+    new_authority: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = CTokenInstruction::ChangeAuthority.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new(*config, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(*new_authority, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `ChangeFee` instruction. The fee collector is required for a
+/// non zero fee
+pub fn change_fee(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    owner: &Pubkey,
+    fee_collector: Option<&Pubkey>,
+    fee: u64,
+) -> Result<Instruction, ProgramError> {
+    let data = CTokenInstruction::ChangeFee { fee }.try_to_vec()?;
+
+    let mut accounts = vec![
+        AccountMeta::new(*config, false),
+        AccountMeta::new_readonly(*owner, true),
+    ];
+    if fee > 0 {
+        let fee_collector = fee_collector.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        accounts.push(AccountMeta::new_readonly(*fee_collector, false));
+    }
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `Create` instruction for the cToken at the address of `config`,
+/// `token_mint` and `destination`. `token_account` is the vault of lock
+/// cTokens, the mint of mint cTokens and the cToken authority of native
+/// cTokens. `recipient_format` is usually
+/// `RecipientFormat::for_destination(destination)`
+#[allow(clippy::too_many_arguments)]
+pub fn create(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    token_mint: &Pubkey,
+    token_account: &Pubkey,
+    token_program_id: &Pubkey,
+    owner: &Pubkey,
+    payer: &Pubkey,
+    destination: u32,
+    max: u64,
+    min: u64,
+    mode: CTokenMode,
+    remote_decimals: u8,
+    recipient_format: RecipientFormat,
+) -> Result<Instruction, ProgramError> {
+    let (c_token, _) = CToken::find_address(program_id, config, token_mint, destination);
+    let (authority, _) = CToken::find_authority_address(program_id, &c_token);
+    let data = CTokenInstruction::Create {
+        destination,
+        max,
+        min,
+        mode,
+        remote_decimals,
+        recipient_format,
+    }
+    .try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new(c_token, false),
+        if mode == CTokenMode::Native {
+            AccountMeta::new(authority, false)
+        } else {
+            AccountMeta::new_readonly(authority, false)
+        },
+        AccountMeta::new_readonly(*token_mint, false),
+        AccountMeta::new_readonly(*token_account, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(*config, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `ChangeLimit` instruction
+pub fn change_limit(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    c_token: &Pubkey,
+    owner: &Pubkey,
+    max: u64,
+    min: u64,
+) -> Result<Instruction, ProgramError> {
+    let data = CTokenInstruction::ChangeLimit { max, min }.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*config, false),
+        AccountMeta::new(*c_token, false),
+        AccountMeta::new_readonly(*owner, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `Bridge` instruction. The blocked account of
+/// `user_transfer_authority` follows the one of `user_owner` when they
+/// differ. `route` is required when `destination`
+/// is not the cToken destination, `fee_accounts` (payer and config fee
+/// collector) when a lamport fee is charged, `fee_token` when the cToken
+/// charges a token fee and `receipt` (payer, bridge index) when the cToken
+/// writes bridge receipts. The bridge index is the cToken index after the
+/// bridge. Accounts required by the mint go in `additional_accounts`
+#[allow(clippy::too_many_arguments)]
+pub fn bridge(
+    program_id: &Pubkey,
+    c_token: &Pubkey,
+    c_token_token_account: &Pubkey,
+    user_account: &Pubkey,
+    user_owner: &Pubkey,
+    user_transfer_authority: &Pubkey,
+    token_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    config: &Pubkey,
+    route: Option<&Pubkey>,
+    fee_accounts: Option<(&Pubkey, &Pubkey)>,
+    fee_token: Option<&Pubkey>,
+    receipt: Option<(&Pubkey, u64)>,
+    additional_accounts: &[AccountMeta],
+    destination: u32,
+    amount: u64,
+    recipient: String,
+    payload: Vec<u8>,
+) -> Result<Instruction, ProgramError> {
+    let (blocked_account, _) = BlockedAccount::find_address(program_id, config, user_owner);
+    let data = CTokenInstruction::Bridge {
+        destination,
+        amount,
+        recipient,
+        payload,
+    }
+    .try_to_vec()?;
+
+    let mut accounts = vec![
+        AccountMeta::new(*c_token, false),
+        AccountMeta::new(*c_token_token_account, false),
+        AccountMeta::new(*user_account, false),
+        AccountMeta::new_readonly(*user_transfer_authority, true),
+        AccountMeta::new(*token_mint, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(*config, false),
+        AccountMeta::new_readonly(blocked_account, false),
+    ];
+    if user_transfer_authority != user_owner {
+        let (blocked_account, _) =
+            BlockedAccount::find_address(program_id, config, user_transfer_authority);
+        accounts.push(AccountMeta::new_readonly(blocked_account, false));
+    }
+    if let Some(route) = route {
+        accounts.push(AccountMeta::new_readonly(*route, false));
+    }
+    if let Some((payer, fee_collector)) = fee_accounts {
+        accounts.push(AccountMeta::new(*payer, true));
+        accounts.push(AccountMeta::new(*fee_collector, false));
+    }
+    if let Some(fee_token) = fee_token {
+        accounts.push(AccountMeta::new(*fee_token, false));
+    }
+    if let Some((payer, index)) = receipt {
+        let (receipt, _) = BridgeReceipt::find_address(program_id, c_token, index);
+        accounts.push(AccountMeta::new(receipt, false));
+        accounts.push(AccountMeta::new(*payer, true));
+        accounts.push(AccountMeta::new_readonly(system_program::id(), false));
+    }
+    accounts.extend_from_slice(additional_accounts);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
 }
 
+/// Creates a `Settle` instruction paying `amount` in remote decimals to
+/// `user_account`
+#[allow(clippy::too_many_arguments)]
 pub fn settle(
     program_id: &Pubkey,
     c_token: &Pubkey,
     token_authority: &Pubkey,
     c_token_token_account: &Pubkey,
     user_account: &Pubkey,
+    user_owner: &Pubkey,
     authorith: &Pubkey,
     token_mint: &Pubkey,
     config: &Pubkey,
     token_program_id: &Pubkey,
-    amount: u64,
+    payer: &Pubkey,
+    amount: u128,
+    transfer_id: TransferId,
 ) -> Result<Instruction, ProgramError> {
-    let data = CTokenInstruction::Settle { amount }.try_to_vec()?;
+    let (receipt, _) = SettlementReceipt::find_address(program_id, config, &transfer_id);
+    let (blocked_account, _) = BlockedAccount::find_address(program_id, config, user_owner);
+    let data = CTokenInstruction::Settle {
+        amount,
+        transfer_id,
+    }
+    .try_to_vec()?;
 
     let mut accounts = vec![
-        AccountMeta::new_readonly(*c_token, false),
+        AccountMeta::new(*c_token, false),
         AccountMeta::new_readonly(*token_authority, false),
         AccountMeta::new(*c_token_token_account, false),
         AccountMeta::new(*user_account, false),
@@ -61,6 +473,10 @@ pub fn settle(
         AccountMeta::new(*token_mint, false),
         AccountMeta::new_readonly(*token_program_id, false),
         AccountMeta::new_readonly(*config, false),
+        AccountMeta::new(receipt, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(blocked_account, false),
     ];
 
     if &system_program::id() == c_token_token_account {
@@ -73,3 +489,916 @@ pub fn settle(
         data,
     })
 }
+
+/// Creates a `Settle` instruction paying `amount` in remote decimals to the
+/// associated token account of `wallet`, which is created when missing
+#[allow(clippy::too_many_arguments)]
+pub fn settle_to_wallet(
+    program_id: &Pubkey,
+    c_token: &Pubkey,
+    token_authority: &Pubkey,
+    c_token_token_account: &Pubkey,
+    wallet: &Pubkey,
+    authority: &Pubkey,
+    token_mint: &Pubkey,
+    config: &Pubkey,
+    token_program_id: &Pubkey,
+    payer: &Pubkey,
+    amount: u128,
+    transfer_id: TransferId,
+) -> Result<Instruction, ProgramError> {
+    let mut instruction = settle(
+        program_id,
+        c_token,
+        token_authority,
+        c_token_token_account,
+        wallet,
+        wallet,
+        authority,
+        token_mint,
+        config,
+        token_program_id,
+        payer,
+        amount,
+        transfer_id,
+    )?;
+    let (rent_vault, _) = Config::find_rent_vault_address(program_id, config);
+    instruction.accounts.extend([
+        AccountMeta::new(
+            get_associated_token_address_with_program_id(wallet, token_mint, token_program_id),
+            false,
+        ),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new(rent_vault, false),
+    ]);
+
+    Ok(instruction)
+}
+
+/// Appends the drop-off accounts to a `Settle` instruction of a cToken with
+/// a drop-off, before any Token-2022 additional accounts. `wallet` is the
+/// recipient wallet, the owner of a recipient token account.
+pub fn with_drop_off(
+    mut instruction: Instruction,
+    config: &Pubkey,
+    wallet: &Pubkey,
+) -> Instruction {
+    let (drop_off_vault, _) = Config::find_drop_off_vault_address(&instruction.program_id, config);
+    instruction.accounts.extend([
+        AccountMeta::new(*wallet, false),
+        AccountMeta::new(drop_off_vault, false),
+    ]);
+    instruction
+}
+
+fn set_paused(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    owner: &Pubkey,
+    target: Option<(&Pubkey, Option<u32>)>,
+    paused: bool,
+) -> Result<Instruction, ProgramError> {
+    let data = if paused {
+        CTokenInstruction::Pause
+    } else {
+        CTokenInstruction::Unpause
+    }
+    .try_to_vec()?;
+
+    let mut accounts = vec![
+        AccountMeta::new(*config, false),
+        AccountMeta::new_readonly(*owner, true),
+    ];
+    if let Some((c_token, destination)) = target {
+        accounts.push(AccountMeta::new(*c_token, false));
+        if let Some(destination) = destination {
+            let (route, _) = Route::find_address(program_id, c_token, destination);
+            accounts.push(AccountMeta::new(route, false));
+        }
+    }
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `Pause` instruction pausing the config, or the cToken of
+/// `target` or its route to the destination when given
+pub fn pause(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    owner: &Pubkey,
+    target: Option<(&Pubkey, Option<u32>)>,
+) -> Result<Instruction, ProgramError> {
+    set_paused(program_id, config, owner, target, true)
+}
+
+/// Creates an `Unpause` instruction unpausing the config, or the cToken of
+/// `target` or its route to the destination when given
+pub fn unpause(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    owner: &Pubkey,
+    target: Option<(&Pubkey, Option<u32>)>,
+) -> Result<Instruction, ProgramError> {
+    set_paused(program_id, config, owner, target, false)
+}
+
+/// Creates a `ChangeVolumeLimit` instruction
+pub fn change_volume_limit(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    c_token: &Pubkey,
+    owner: &Pubkey,
+    window: i64,
+    outbound: u64,
+    inbound: u64,
+) -> Result<Instruction, ProgramError> {
+    let data = CTokenInstruction::ChangeVolumeLimit {
+        window,
+        outbound,
+        inbound,
+    }
+    .try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*config, false),
+        AccountMeta::new(*c_token, false),
+        AccountMeta::new_readonly(*owner, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `ChangeTokenFee` instruction. `fee_token` is required for a
+/// non zero fee, a token account of the cToken authority or a system
+/// account for native cTokens
+#[allow(clippy::too_many_arguments)]
+pub fn change_token_fee(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    c_token: &Pubkey,
+    owner: &Pubkey,
+    fee_token: Option<&Pubkey>,
+    bps: u16,
+    min: u64,
+    max: u64,
+) -> Result<Instruction, ProgramError> {
+    let data = CTokenInstruction::ChangeTokenFee { bps, min, max }.try_to_vec()?;
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*config, false),
+        AccountMeta::new(*c_token, false),
+        AccountMeta::new_readonly(*owner, true),
+    ];
+    if bps > 0 || min > 0 {
+        let fee_token = fee_token.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        accounts.push(AccountMeta::new_readonly(*fee_token, false));
+    }
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `WithdrawTokenFee` instruction moving `amount` out of the fee
+/// token account to `destination`. Accounts required by the mint go in
+/// `additional_accounts`
+#[allow(clippy::too_many_arguments)]
+pub fn withdraw_token_fee(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    c_token: &Pubkey,
+    owner: &Pubkey,
+    fee_token: &Pubkey,
+    destination: &Pubkey,
+    token_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    additional_accounts: &[AccountMeta],
+    amount: u64,
+) -> Result<Instruction, ProgramError> {
+    let (authority, _) = CToken::find_authority_address(program_id, c_token);
+    let data = CTokenInstruction::WithdrawTokenFee { amount }.try_to_vec()?;
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*config, false),
+        AccountMeta::new_readonly(*c_token, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(authority, false),
+        AccountMeta::new(*fee_token, false),
+        AccountMeta::new(*destination, false),
+        AccountMeta::new_readonly(*token_mint, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+    ];
+    accounts.extend_from_slice(additional_accounts);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an `AcceptOwner` instruction signed by the pending owner
+pub fn accept_owner(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    new_owner: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = CTokenInstruction::AcceptOwner.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new(*config, false),
+        AccountMeta::new_readonly(*new_owner, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an `AcceptAuthority` instruction signed by the pending authority
+pub fn accept_authority(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    new_authority: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = CTokenInstruction::AcceptAuthority.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new(*config, false),
+        AccountMeta::new_readonly(*new_authority, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `CancelPendingOwner` instruction
+pub fn cancel_pending_owner(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    owner: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = CTokenInstruction::CancelPendingOwner.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new(*config, false),
+        AccountMeta::new_readonly(*owner, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `CancelPendingAuthority` instruction
+pub fn cancel_pending_authority(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    owner: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = CTokenInstruction::CancelPendingAuthority.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new(*config, false),
+        AccountMeta::new_readonly(*owner, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `Migrate` instruction for `config` and the (cToken, token mint)
+/// pairs of `c_tokens`
+pub fn migrate(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    owner: &Pubkey,
+    payer: &Pubkey,
+    c_tokens: &[(Pubkey, Pubkey)],
+    remote_decimals: u8,
+) -> Result<Instruction, ProgramError> {
+    let data = CTokenInstruction::Migrate { remote_decimals }.try_to_vec()?;
+
+    let mut accounts = vec![
+        AccountMeta::new(*config, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    for (c_token, token_mint) in c_tokens {
+        accounts.push(AccountMeta::new(*c_token, false));
+        accounts.push(AccountMeta::new_readonly(*token_mint, false));
+    }
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `CloseCToken` instruction. `destination` receives the vault
+/// balance of lock and native cTokens and `new_mint_authority` the mint
+/// authority of mint cTokens, while `receiver` gets the rent. `fee_token` is
+/// required when a lock or mint cToken has a fee token account. Accounts
+/// required by the mint go in `additional_accounts`
+#[allow(clippy::too_many_arguments)]
+pub fn close_c_token(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    c_token: &Pubkey,
+    owner: &Pubkey,
+    c_token_token_account: &Pubkey,
+    destination: &Pubkey,
+    token_mint: &Pubkey,
+    new_mint_authority: &Pubkey,
+    token_program_id: &Pubkey,
+    receiver: &Pubkey,
+    fee_token: Option<&Pubkey>,
+    additional_accounts: &[AccountMeta],
+) -> Result<Instruction, ProgramError> {
+    let (authority, _) = CToken::find_authority_address(program_id, c_token);
+    let data = CTokenInstruction::CloseCToken.try_to_vec()?;
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*config, false),
+        AccountMeta::new(*c_token, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(authority, false),
+        AccountMeta::new(*c_token_token_account, false),
+        AccountMeta::new(*destination, false),
+        AccountMeta::new(*token_mint, false),
+        AccountMeta::new_readonly(*new_mint_authority, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new(*receiver, false),
+    ];
+    if let Some(fee_token) = fee_token {
+        accounts.push(AccountMeta::new_readonly(*fee_token, false));
+    }
+    accounts.extend_from_slice(additional_accounts);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `SetLiquidityProvider` instruction
+pub fn set_liquidity_provider(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    c_token: &Pubkey,
+    owner: &Pubkey,
+    provider: &Pubkey,
+    payer: &Pubkey,
+    enabled: bool,
+) -> Result<Instruction, ProgramError> {
+    let (liquidity_provider, _) = LiquidityProvider::find_address(program_id, c_token, provider);
+    let data = CTokenInstruction::SetLiquidityProvider { enabled }.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*config, false),
+        AccountMeta::new_readonly(*c_token, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(*provider, false),
+        AccountMeta::new(liquidity_provider, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an `AddLiquidity` instruction moving `amount` from `source` of
+/// `provider` into the vault. Accounts required by the mint go in
+/// `additional_accounts`
+#[allow(clippy::too_many_arguments)]
+pub fn add_liquidity(
+    program_id: &Pubkey,
+    c_token: &Pubkey,
+    provider: &Pubkey,
+    source: &Pubkey,
+    c_token_token_account: &Pubkey,
+    token_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    config: &Pubkey,
+    additional_accounts: &[AccountMeta],
+    amount: u64,
+) -> Result<Instruction, ProgramError> {
+    let (liquidity_provider, _) = LiquidityProvider::find_address(program_id, c_token, provider);
+    let data = CTokenInstruction::AddLiquidity { amount }.try_to_vec()?;
+
+    let mut accounts = vec![
+        AccountMeta::new(*c_token, false),
+        AccountMeta::new(liquidity_provider, false),
+        AccountMeta::new_readonly(*provider, true),
+        AccountMeta::new(*source, false),
+        AccountMeta::new(*c_token_token_account, false),
+        AccountMeta::new_readonly(*token_mint, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(*config, false),
+    ];
+    accounts.extend_from_slice(additional_accounts);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `RemoveLiquidity` instruction moving `amount` of `provider` out
+/// of the vault to `destination`. Accounts required by the mint go in
+/// `additional_accounts`
+#[allow(clippy::too_many_arguments)]
+pub fn remove_liquidity(
+    program_id: &Pubkey,
+    c_token: &Pubkey,
+    provider: &Pubkey,
+    c_token_token_account: &Pubkey,
+    destination: &Pubkey,
+    token_mint: &Pubkey,
+    token_program_id: &Pubkey,
+    config: &Pubkey,
+    additional_accounts: &[AccountMeta],
+    amount: u64,
+) -> Result<Instruction, ProgramError> {
+    let (liquidity_provider, _) = LiquidityProvider::find_address(program_id, c_token, provider);
+    let (authority, _) = CToken::find_authority_address(program_id, c_token);
+    let data = CTokenInstruction::RemoveLiquidity { amount }.try_to_vec()?;
+
+    let mut accounts = vec![
+        AccountMeta::new(*c_token, false),
+        AccountMeta::new(liquidity_provider, false),
+        AccountMeta::new_readonly(*provider, true),
+        AccountMeta::new_readonly(authority, false),
+        AccountMeta::new(*c_token_token_account, false),
+        AccountMeta::new(*destination, false),
+        AccountMeta::new_readonly(*token_mint, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(*config, false),
+    ];
+    accounts.extend_from_slice(additional_accounts);
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `ChangeLiquidityReserve` instruction
+pub fn change_liquidity_reserve(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    c_token: &Pubkey,
+    owner: &Pubkey,
+    reserve: u64,
+) -> Result<Instruction, ProgramError> {
+    let data = CTokenInstruction::ChangeLiquidityReserve { reserve }.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*config, false),
+        AccountMeta::new(*c_token, false),
+        AccountMeta::new_readonly(*owner, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an `AddRoute` instruction for the route of `c_token` to
+/// `destination`. `recipient_format` is usually
+/// `RecipientFormat::for_destination(destination)`
+#[allow(clippy::too_many_arguments)]
+pub fn add_route(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    c_token: &Pubkey,
+    owner: &Pubkey,
+    payer: &Pubkey,
+    destination: u32,
+    max: u64,
+    min: u64,
+    fee: u64,
+    recipient_format: RecipientFormat,
+) -> Result<Instruction, ProgramError> {
+    let (route, _) = Route::find_address(program_id, c_token, destination);
+    let data = CTokenInstruction::AddRoute {
+        destination,
+        max,
+        min,
+        fee,
+        recipient_format,
+    }
+    .try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*config, false),
+        AccountMeta::new_readonly(*c_token, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new(route, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `RemoveRoute` instruction, `receiver` gets the rent
+pub fn remove_route(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    c_token: &Pubkey,
+    owner: &Pubkey,
+    receiver: &Pubkey,
+    destination: u32,
+) -> Result<Instruction, ProgramError> {
+    let (route, _) = Route::find_address(program_id, c_token, destination);
+    let data = CTokenInstruction::RemoveRoute.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*config, false),
+        AccountMeta::new_readonly(*c_token, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new(route, false),
+        AccountMeta::new(*receiver, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `ChangeDecimals` instruction
+pub fn change_decimals(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    c_token: &Pubkey,
+    owner: &Pubkey,
+    token_mint: &Pubkey,
+    remote_decimals: u8,
+) -> Result<Instruction, ProgramError> {
+    let data = CTokenInstruction::ChangeDecimals { remote_decimals }.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*config, false),
+        AccountMeta::new(*c_token, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(*token_mint, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `Block` instruction blocking `account`
+pub fn block(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    owner: &Pubkey,
+    payer: &Pubkey,
+    account: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let (blocked_account, _) = BlockedAccount::find_address(program_id, config, account);
+    let data = CTokenInstruction::Block { account: *account }.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*config, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new(blocked_account, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an `Unblock` instruction unblocking `account`, `receiver` gets
+/// the rent
+pub fn unblock(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    owner: &Pubkey,
+    receiver: &Pubkey,
+    account: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let (blocked_account, _) = BlockedAccount::find_address(program_id, config, account);
+    let data = CTokenInstruction::Unblock.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*config, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new(blocked_account, false),
+        AccountMeta::new(*receiver, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `SettleAndCall` instruction paying `amount` in remote decimals
+/// to `user_account`, then invoking `receiver_program_id` with
+/// `additional_accounts`
+#[allow(clippy::too_many_arguments)]
+pub fn settle_and_call(
+    program_id: &Pubkey,
+    c_token: &Pubkey,
+    token_authority: &Pubkey,
+    c_token_token_account: &Pubkey,
+    user_account: &Pubkey,
+    user_owner: &Pubkey,
+    authority: &Pubkey,
+    token_mint: &Pubkey,
+    config: &Pubkey,
+    token_program_id: &Pubkey,
+    payer: &Pubkey,
+    receiver_program_id: &Pubkey,
+    additional_accounts: &[AccountMeta],
+    amount: u128,
+    transfer_id: TransferId,
+    sender: Vec<u8>,
+    payload: Vec<u8>,
+) -> Result<Instruction, ProgramError> {
+    let mut instruction = settle(
+        program_id,
+        c_token,
+        token_authority,
+        c_token_token_account,
+        user_account,
+        user_owner,
+        authority,
+        token_mint,
+        config,
+        token_program_id,
+        payer,
+        amount,
+        transfer_id.clone(),
+    )?;
+    instruction.data = CTokenInstruction::SettleAndCall {
+        amount,
+        transfer_id,
+        sender,
+        payload,
+    }
+    .try_to_vec()?;
+
+    let (caller, _) = find_caller_address(program_id, c_token);
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(*receiver_program_id, false));
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(caller, false));
+    instruction.accounts.extend_from_slice(additional_accounts);
+
+    Ok(instruction)
+}
+
+/// Creates a `SettleBatch` instruction paying each entry to the account of
+/// `recipients` at its recipient index, given as (account, owner) pairs. A
+/// wallet recipient of a lock or mint cToken, its own owner, is paid to its
+/// associated token account
+#[allow(clippy::too_many_arguments)]
+pub fn settle_batch(
+    program_id: &Pubkey,
+    c_token: &Pubkey,
+    token_authority: &Pubkey,
+    c_token_token_account: &Pubkey,
+    authority: &Pubkey,
+    token_mint: &Pubkey,
+    config: &Pubkey,
+    token_program_id: &Pubkey,
+    payer: &Pubkey,
+    recipients: &[(Pubkey, Pubkey)],
+    entries: Vec<SettleEntry>,
+) -> Result<Instruction, ProgramError> {
+    let mut accounts = vec![
+        AccountMeta::new(*c_token, false),
+        AccountMeta::new_readonly(*token_authority, false),
+        AccountMeta::new(*c_token_token_account, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(*token_mint, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(*config, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new(Config::find_rent_vault_address(program_id, config).0, false),
+    ];
+    if &system_program::id() == c_token_token_account {
+        accounts[2] = AccountMeta::new_readonly(*c_token_token_account, false)
+    }
+    for entry in &entries {
+        let (receipt, _) = SettlementReceipt::find_address(program_id, config, &entry.transfer_id);
+        accounts.push(AccountMeta::new(receipt, false));
+    }
+    for (recipient, owner) in recipients {
+        let (blocked_account, _) = BlockedAccount::find_address(program_id, config, owner);
+        let token_account = if recipient == owner && *token_program_id != system_program::id() {
+            get_associated_token_address_with_program_id(owner, token_mint, token_program_id)
+        } else {
+            *recipient
+        };
+        accounts.push(AccountMeta::new(*recipient, false));
+        accounts.push(AccountMeta::new(token_account, false));
+        accounts.push(AccountMeta::new_readonly(blocked_account, false));
+    }
+    let data = CTokenInstruction::SettleBatch { entries }.try_to_vec()?;
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `WithdrawRentVault` instruction
+pub fn withdraw_rent_vault(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    owner: &Pubkey,
+    destination: &Pubkey,
+    amount: u64,
+) -> Result<Instruction, ProgramError> {
+    let (rent_vault, _) = Config::find_rent_vault_address(program_id, config);
+    let data = CTokenInstruction::WithdrawRentVault { amount }.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*config, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new(rent_vault, false),
+        AccountMeta::new(*destination, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `ChangeDropOff` instruction
+pub fn change_drop_off(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    c_token: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+    threshold: u64,
+) -> Result<Instruction, ProgramError> {
+    let data = CTokenInstruction::ChangeDropOff { amount, threshold }.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*config, false),
+        AccountMeta::new(*c_token, false),
+        AccountMeta::new_readonly(*owner, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `FundDropOffVault` instruction
+pub fn fund_drop_off_vault(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> Result<Instruction, ProgramError> {
+    let (drop_off_vault, _) = Config::find_drop_off_vault_address(program_id, config);
+    let data = CTokenInstruction::FundDropOffVault { amount }.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*config, false),
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(drop_off_vault, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `WithdrawDropOffVault` instruction
+pub fn withdraw_drop_off_vault(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    owner: &Pubkey,
+    destination: &Pubkey,
+    amount: u64,
+) -> Result<Instruction, ProgramError> {
+    let (drop_off_vault, _) = Config::find_drop_off_vault_address(program_id, config);
+    let data = CTokenInstruction::WithdrawDropOffVault { amount }.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*config, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new(drop_off_vault, false),
+        AccountMeta::new(*destination, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `ChangeReceiptAge` instruction
+pub fn change_receipt_age(
+    program_id: &Pubkey,
+    config: &Pubkey,
+    c_token: &Pubkey,
+    owner: &Pubkey,
+    age: i64,
+) -> Result<Instruction, ProgramError> {
+    let data = CTokenInstruction::ChangeReceiptAge { age }.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*config, false),
+        AccountMeta::new(*c_token, false),
+        AccountMeta::new_readonly(*owner, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `CloseBridgeReceipt` instruction, which anyone can send
+pub fn close_bridge_receipt(
+    program_id: &Pubkey,
+    c_token: &Pubkey,
+    index: u64,
+    payer: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let (receipt, _) = BridgeReceipt::find_address(program_id, c_token, index);
+    let data = CTokenInstruction::CloseBridgeReceipt.try_to_vec()?;
+
+    let accounts = vec![
+        AccountMeta::new(*c_token, false),
+        AccountMeta::new(receipt, false),
+        AccountMeta::new(*payer, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
@@ -1,8 +1,13 @@
 #[cfg(not(feature = "no-entrypoint"))]
 mod entrypoint;
-mod log;
 
 pub mod error;
 pub mod instruction;
+pub mod log;
 pub mod processor;
+pub mod receiver;
+pub mod recipient;
 pub mod state;
+
+// Program id of the mainnet deployment
+solana_program::declare_id!("A9SGRcytnfx6U1QrnMUwK5sxYyCYY3MpyrPcyeafhSMF");
@@ -1,10 +1,15 @@
 use std::fmt;
 
 use borsh::{to_vec, BorshDeserialize, BorshSerialize};
-use solana_program::pubkey::Pubkey;
+use solana_program::{log::sol_log_data, pubkey::Pubkey};
 
-/// Crosschain global config
-#[derive(Clone, Debug, BorshDeserialize, BorshSerialize)]
+use crate::state::{CToken, Config, TransferId};
+
+/// Version of the event encoding, the first byte of every event log
+pub const EVENT_VERSION: u8 = 1;
+
+/// Bridge event
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
 pub struct Bridge {
     /// Token mint
     pub token: Pubkey,
@@ -18,7 +23,7 @@ pub struct Bridge {
     /// Recipient
     pub recipient: String,
 
-    /// Amount
+    /// Amount after token fee
     pub amount: u64,
 
     /// Fee
@@ -29,6 +34,12 @@ pub struct Bridge {
 
     /// Payload
     pub payload: Vec<u8>,
+
+    /// Token fee
+    pub token_fee: u64,
+
+    /// Amount scaled to remote decimals
+    pub remote_amount: u128,
 }
 
 impl fmt::Display for Bridge {
@@ -36,3 +47,437 @@ impl fmt::Display for Bridge {
         write!(f, "{}", hex::encode(to_vec(&self).unwrap()))
     }
 }
+
+/// Settle event
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct Settle {
+    /// Token mint
+    pub token: Pubkey,
+
+    /// Settled cToken
+    pub c_token: Pubkey,
+
+    /// Source transfer
+    pub transfer_id: TransferId,
+
+    /// Recipient token account
+    pub recipient: Pubkey,
+
+    /// Amount
+    pub amount: u64,
+}
+
+/// Config state after a change
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct ConfigChanged {
+    /// Config
+    pub config: Pubkey,
+
+    /// Owner
+    pub owner: Pubkey,
+
+    /// Authority
+    pub authority: Pubkey,
+
+    /// Bridge fee
+    pub fee: u64,
+
+    /// Fee collector
+    pub fee_collector: Pubkey,
+
+    /// Proposed owner
+    pub pending_owner: Option<Pubkey>,
+
+    /// Proposed authority
+    pub pending_authority: Option<Pubkey>,
+}
+
+impl ConfigChanged {
+    pub fn new(key: &Pubkey, config: &Config) -> Self {
+        Self {
+            config: *key,
+            owner: config.owner,
+            authority: config.authority,
+            fee: config.fee,
+            fee_collector: config.fee_collector,
+            pending_owner: config.pending_owner,
+            pending_authority: config.pending_authority,
+        }
+    }
+}
+
+/// CToken created
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct Created {
+    /// Config
+    pub config: Pubkey,
+
+    /// CToken
+    pub c_token: Pubkey,
+
+    /// Token mint
+    pub token: Pubkey,
+
+    /// Destination
+    pub destination: u32,
+
+    /// Max amount
+    pub max: u64,
+
+    /// Min amount
+    pub min: u64,
+}
+
+/// Bridge and volume limits after a change
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct LimitChanged {
+    /// CToken
+    pub c_token: Pubkey,
+
+    /// Max amount
+    pub max: u64,
+
+    /// Min amount
+    pub min: u64,
+
+    /// Volume limit window in seconds
+    pub window: i64,
+
+    /// Outbound volume cap
+    pub outbound: u64,
+
+    /// Inbound volume cap
+    pub inbound: u64,
+}
+
+impl LimitChanged {
+    pub fn new(key: &Pubkey, c_token: &CToken) -> Self {
+        Self {
+            c_token: *key,
+            max: c_token.max,
+            min: c_token.min,
+            window: c_token.window,
+            outbound: c_token.outbound.cap,
+            inbound: c_token.inbound.cap,
+        }
+    }
+}
+
+/// Token fee after a change
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct TokenFeeChanged {
+    /// CToken
+    pub c_token: Pubkey,
+
+    /// Basis points of amount
+    pub bps: u16,
+
+    /// Min fee
+    pub min: u64,
+
+    /// Max fee
+    pub max: u64,
+
+    /// Fee token account
+    pub fee_token: Pubkey,
+}
+
+/// Token fee withdrawn
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct TokenFeeWithdrawn {
+    /// CToken
+    pub c_token: Pubkey,
+
+    /// Destination token account
+    pub destination: Pubkey,
+
+    /// Amount
+    pub amount: u64,
+}
+
+/// Lamports withdrawn from the rent vault of a config
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct RentVaultWithdrawn {
+    /// Config
+    pub config: Pubkey,
+
+    /// Destination account
+    pub destination: Pubkey,
+
+    /// Lamports
+    pub amount: u64,
+}
+
+/// Drop-off of a cToken after a change
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct DropOffChanged {
+    /// CToken
+    pub c_token: Pubkey,
+
+    /// Lamports paid to recipient wallets
+    pub amount: u64,
+
+    /// Wallet balance below which the drop-off is paid
+    pub threshold: u64,
+}
+
+/// Lamports moved into or out of the drop-off vault of a config
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct DropOffVault {
+    /// Config
+    pub config: Pubkey,
+
+    /// Owner funding the vault, or the withdrawal destination
+    pub account: Pubkey,
+
+    /// Lamports
+    pub amount: u64,
+}
+
+/// Drop-off paid to a recipient wallet on settle
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct DroppedOff {
+    /// Settled cToken
+    pub c_token: Pubkey,
+
+    /// Recipient wallet
+    pub wallet: Pubkey,
+
+    /// Lamports
+    pub amount: u64,
+}
+
+/// Config or cToken paused or unpaused
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct PauseChanged {
+    /// Config or cToken
+    pub account: Pubkey,
+
+    /// Paused state
+    pub paused: bool,
+}
+
+/// Account migrated to a layout version
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct Migrated {
+    /// Config or cToken
+    pub account: Pubkey,
+
+    /// Layout version
+    pub version: u8,
+}
+
+/// CToken closed
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct Closed {
+    /// CToken
+    pub c_token: Pubkey,
+
+    /// Token account receiving the vault balance of a lock cToken
+    pub destination: Pubkey,
+
+    /// Vault balance moved to destination
+    pub amount: u64,
+
+    /// New mint authority of a mint cToken
+    pub mint_authority: Pubkey,
+}
+
+/// Liquidity provider whitelisted or delisted
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct LiquidityProviderChanged {
+    /// CToken
+    pub c_token: Pubkey,
+
+    /// Provider
+    pub provider: Pubkey,
+
+    /// Whitelisted
+    pub enabled: bool,
+}
+
+/// Liquidity added to or removed from a vault
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct Liquidity {
+    /// CToken
+    pub c_token: Pubkey,
+
+    /// Provider
+    pub provider: Pubkey,
+
+    /// Amount
+    pub amount: u64,
+
+    /// Liquidity of the provider after the change
+    pub provided: u64,
+}
+
+/// Liquidity reserve after a change
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct LiquidityReserveChanged {
+    /// CToken
+    pub c_token: Pubkey,
+
+    /// Reserve
+    pub reserve: u64,
+}
+
+/// Route added, updated or removed
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct RouteChanged {
+    /// CToken
+    pub c_token: Pubkey,
+
+    /// Destination
+    pub destination: u32,
+
+    /// Max amount
+    pub max: u64,
+
+    /// Min amount
+    pub min: u64,
+
+    /// Bridge fee in lamports
+    pub fee: u64,
+
+    /// Whether the route exists after the change
+    pub enabled: bool,
+}
+
+/// Decimals after a change
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct DecimalsChanged {
+    /// CToken
+    pub c_token: Pubkey,
+
+    /// Decimals of the token mint
+    pub decimals: u8,
+
+    /// Decimals on the destination chain
+    pub remote_decimals: u8,
+}
+
+/// Account blocked or unblocked
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct BlockChanged {
+    /// Config
+    pub config: Pubkey,
+
+    /// Account
+    pub account: Pubkey,
+
+    /// Blocked
+    pub blocked: bool,
+}
+
+/// Receipt age after a change
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct ReceiptAgeChanged {
+    /// CToken
+    pub c_token: Pubkey,
+
+    /// Age in seconds after which bridge receipts can be closed
+    pub age: i64,
+}
+
+/// Bridge receipt closed and its rent refunded
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct BridgeReceiptClosed {
+    /// CToken of the bridge
+    pub c_token: Pubkey,
+
+    /// Receipt account
+    pub receipt: Pubkey,
+
+    /// Bridge index
+    pub index: u64,
+
+    /// Payer of the receipt, receiving the rent
+    pub rent_recipient: Pubkey,
+
+    /// Lamports refunded
+    pub rent: u64,
+}
+
+/// Transfer settled in a batch
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct SettledTransfer {
+    /// Source transfer
+    pub transfer_id: TransferId,
+
+    /// Recipient token account
+    pub recipient: Pubkey,
+
+    /// Amount
+    pub amount: u64,
+}
+
+/// Settle batch event, one for all transfers of a `SettleBatch`
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct SettleBatch {
+    /// Token mint
+    pub token: Pubkey,
+
+    /// Settled cToken
+    pub c_token: Pubkey,
+
+    /// Settled transfers in entry order
+    pub transfers: Vec<SettledTransfer>,
+}
+
+/// Program events, logged with `sol_log_data` as [`EVENT_VERSION`] followed
+/// by the Borsh encoded event, whose first byte is the event discriminator.
+/// New events must be appended to keep discriminators stable.
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub enum Event {
+    Bridge(Bridge),
+    Settle(Settle),
+    ConfigChanged(ConfigChanged),
+    Created(Created),
+    LimitChanged(LimitChanged),
+    TokenFeeChanged(TokenFeeChanged),
+    TokenFeeWithdrawn(TokenFeeWithdrawn),
+    PauseChanged(PauseChanged),
+    Migrated(Migrated),
+    Closed(Closed),
+    LiquidityProviderChanged(LiquidityProviderChanged),
+    LiquidityAdded(Liquidity),
+    LiquidityRemoved(Liquidity),
+    LiquidityReserveChanged(LiquidityReserveChanged),
+    RouteChanged(RouteChanged),
+    DecimalsChanged(DecimalsChanged),
+    BlockChanged(BlockChanged),
+    ReceiptAgeChanged(ReceiptAgeChanged),
+    SettleBatch(SettleBatch),
+    RentVaultWithdrawn(RentVaultWithdrawn),
+    DropOffChanged(DropOffChanged),
+    DropOffVaultFunded(DropOffVault),
+    DropOffVaultWithdrawn(DropOffVault),
+    DroppedOff(DroppedOff),
+    BridgeReceiptClosed(BridgeReceiptClosed),
+}
+
+impl Event {
+    /// Encodes the event as logged
+    pub fn encode(&self) -> Vec<u8> {
+        let mut data = vec![EVENT_VERSION];
+        data.extend(to_vec(self).unwrap());
+        data
+    }
+
+    /// Decodes an event from logged data
+    pub fn decode(data: &[u8]) -> std::io::Result<Self> {
+        match data.split_first() {
+            Some((&EVENT_VERSION, event)) => Self::try_from_slice(event),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "unknown event version",
+            )),
+        }
+    }
+
+    /// Logs the event with `sol_log_data`
+    pub fn emit(&self) {
+        sol_log_data(&[&self.encode()]);
+    }
+}
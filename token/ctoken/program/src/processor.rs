@@ -3,31 +3,50 @@ use std::error::Error;
 use borsh::{BorshDeserialize, BorshSerialize};
 use num_traits::FromPrimitive;
 use solana_program::{
-    account_info::{next_account_info, AccountInfo},
+    account_info::{next_account_info, next_account_infos, AccountInfo},
     decode_error::DecodeError,
     entrypoint::ProgramResult,
-    instruction::Instruction,
-    msg,
+    instruction::{AccountMeta, Instruction},
+    keccak, msg,
     program::{invoke, invoke_signed},
     program_error::{PrintProgramError, ProgramError},
     program_option::COption,
-    pubkey::Pubkey,
-    system_instruction,
+    program_pack::Pack,
+    pubkey::{Pubkey, MAX_SEED_LEN},
+    system_instruction, system_program,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+use spl_associated_token_account::{
+    get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account_idempotent,
 };
 use spl_token_2022::{
     check_spl_token_program_account,
     error::TokenError,
-    extension::StateWithExtensions,
+    extension::{
+        memo_transfer, non_transferable::NonTransferable,
+        permanent_delegate::get_permanent_delegate, BaseStateWithExtensions, ExtensionType,
+        StateWithExtensions,
+    },
+    instruction::AuthorityType,
     state::{Account, Mint},
 };
 
 use crate::{
     error::CTokenError,
-    instruction::CTokenInstruction,
-    log,
-    state::{CToken, Config},
+    instruction::{CTokenInstruction, SettleEntry},
+    log, receiver,
+    recipient::{recipient_address, RecipientFormat},
+    state::{
+        AccountType, BlockedAccount, BridgeReceipt, CToken, CTokenMode, Config, LegacyCToken,
+        LegacyConfig, LiquidityProvider, Route, SettlementReceipt, TransferId, VolumeLimit,
+        NATIVE_MINT,
+    },
 };
 
+/// Memo logged before transfers into accounts requiring memos
+pub const TRANSFER_MEMO: &[u8] = b"cToken transfer";
+
 pub struct Processor {}
 
 impl Processor {
@@ -63,7 +82,22 @@ impl Processor {
         }
     }
 
-    /// transfer token
+    /// Decimals of the cToken mint, SOL decimals in native mode
+    pub fn mint_decimals(
+        c_token: &CToken,
+        token_mint_info: &AccountInfo,
+    ) -> Result<u8, CTokenError> {
+        if c_token.mode == CTokenMode::Native {
+            Ok(spl_token_2022::native_mint::DECIMALS)
+        } else {
+            Self::unpack_mint(token_mint_info, &c_token.token_program_id).map(|m| m.decimals)
+        }
+    }
+
+    /// transfer token, forwarding `additional_accounts` to the transfer hook
+    /// of the mint and logging a memo first if the destination requires one.
+    /// The memo program must be in `additional_accounts` then.
+    #[allow(clippy::too_many_arguments)]
     pub fn token_transfer<'a>(
         c_token: &Pubkey,
         token_program: AccountInfo<'a>,
@@ -71,6 +105,7 @@ impl Processor {
         mint: AccountInfo<'a>,
         destination: AccountInfo<'a>,
         authority: AccountInfo<'a>,
+        additional_accounts: &[AccountInfo<'a>],
         bump_seed: u8,
         amount: u64,
         decimals: u8,
@@ -78,21 +113,125 @@ impl Processor {
         let c_token_bytes = c_token.to_bytes();
         let authority_signature_seeds = [&c_token_bytes[..32], &[bump_seed]];
         let signers = &[&authority_signature_seeds[..]];
-        let ix = spl_token_2022::instruction::transfer_checked(
+
+        let memo_required = {
+            let data = destination.data.borrow();
+            StateWithExtensions::<Account>::unpack(&data)
+                .map(|account| memo_transfer::memo_required(&account))
+                .map_err(|_| CTokenError::ExpectedAccount)?
+        };
+        if memo_required {
+            let memo_program = additional_accounts
+                .iter()
+                .find(|account| *account.key == spl_memo::id())
+                .ok_or(CTokenError::MemoProgramRequired)?;
+            invoke(
+                &spl_memo::build_memo(TRANSFER_MEMO, &[]),
+                &[memo_program.clone()],
+            )?;
+        }
+
+        spl_token_2022::onchain::invoke_transfer_checked(
             token_program.key,
-            source.key,
-            mint.key,
-            destination.key,
-            authority.key,
-            &[],
+            source,
+            mint,
+            destination,
+            authority,
+            additional_accounts,
             amount,
             decimals,
-        )?;
-        invoke_signed_wrapper::<TokenError>(
-            &ix,
-            &[source, mint, destination, authority, token_program],
             signers,
         )
+        .map_err(|err| {
+            err.print::<TokenError>();
+            err
+        })
+    }
+
+    /// Checks that a mint has no extension or authority that lets someone
+    /// else move, freeze or lock up bridged tokens
+    pub fn check_mint_extensions(
+        mint_info: &AccountInfo,
+        c_token_authority: &Pubkey,
+    ) -> Result<(), CTokenError> {
+        let data = mint_info.data.borrow();
+        let mint =
+            StateWithExtensions::<Mint>::unpack(&data).map_err(|_| CTokenError::ExpectedMint)?;
+        if get_permanent_delegate(&mint).is_some() {
+            return Err(CTokenError::UnsupportedMint);
+        }
+        if mint.get_extension::<NonTransferable>().is_ok() {
+            return Err(CTokenError::UnsupportedMint);
+        }
+        if let COption::Some(freeze_authority) = mint.base.freeze_authority {
+            if freeze_authority != *c_token_authority {
+                return Err(CTokenError::UnsupportedMint);
+            }
+        }
+        Ok(())
+    }
+
+    /// Fails if `account` is on the blocklist of `config`. `blocked_info`
+    /// must be the blocklist entry address of `account`, which only exists
+    /// while it is blocked
+    pub fn check_not_blocked(
+        program_id: &Pubkey,
+        config: &Pubkey,
+        account: &Pubkey,
+        blocked_info: &AccountInfo,
+    ) -> ProgramResult {
+        if *blocked_info.key != BlockedAccount::find_address(program_id, config, account).0 {
+            return Err(CTokenError::InvalidProgramAddress.into());
+        }
+        if blocked_info.owner == program_id {
+            return Err(CTokenError::Blocked.into());
+        }
+        Ok(())
+    }
+
+    /// Wallet owning the token account `account_info` of a cToken, the
+    /// account itself for native cTokens
+    fn wallet_of(c_token: &CToken, account_info: &AccountInfo) -> Result<Pubkey, ProgramError> {
+        if c_token.mode == CTokenMode::Native {
+            Ok(*account_info.key)
+        } else {
+            Ok(Self::unpack_token_account(account_info, &c_token.token_program_id)?.owner)
+        }
+    }
+
+    /// Transfer SOL out of the vault of a native cToken, the cToken authority
+    pub fn vault_transfer<'a>(
+        c_token: &Pubkey,
+        vault: AccountInfo<'a>,
+        destination: AccountInfo<'a>,
+        bump_seed: u8,
+        amount: u64,
+    ) -> ProgramResult {
+        let c_token_bytes = c_token.to_bytes();
+        let authority_signature_seeds = [&c_token_bytes[..32], &[bump_seed]];
+        let signers = &[&authority_signature_seeds[..]];
+        invoke_signed(
+            &system_instruction::transfer(vault.key, destination.key, amount),
+            &[vault, destination],
+            signers,
+        )
+    }
+
+    /// Transfer SOL out of a lamport vault of a config at `seed`
+    pub fn config_vault_transfer<'a>(
+        config: &Pubkey,
+        seed: &[u8],
+        bump_seed: u8,
+        vault: &AccountInfo<'a>,
+        destination: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        amount: u64,
+    ) -> ProgramResult {
+        invoke_signed(
+            &system_instruction::transfer(vault.key, destination.key, amount),
+            &[vault.clone(), destination.clone(), system_program.clone()],
+            &[&[seed, &config.to_bytes(), &[bump_seed]]],
+        )
     }
 
     /// Issue a spl_token `Burn` instruction.
@@ -154,6 +293,110 @@ impl Processor {
         )
     }
 
+    /// Issue a spl_token `SetAuthority` instruction for the mint authority.
+    pub fn token_set_mint_authority<'a>(
+        c_token: &Pubkey,
+        token_program: AccountInfo<'a>,
+        mint: AccountInfo<'a>,
+        new_authority: &Pubkey,
+        authority: AccountInfo<'a>,
+        bump_seed: u8,
+    ) -> Result<(), ProgramError> {
+        let c_token_bytes = c_token.to_bytes();
+        let authority_signature_seeds = [&c_token_bytes[..32], &[bump_seed]];
+        let signers = &[&authority_signature_seeds[..]];
+        let ix = spl_token_2022::instruction::set_authority(
+            token_program.key,
+            mint.key,
+            Some(new_authority),
+            AuthorityType::MintTokens,
+            authority.key,
+            &[],
+        )?;
+
+        invoke_signed_wrapper::<TokenError>(&ix, &[mint, authority, token_program], signers)
+    }
+
+    /// Closes the token account `account` of the cToken authority, sending
+    /// its rent to `destination`
+    pub fn token_close_account<'a>(
+        c_token: &Pubkey,
+        token_program: AccountInfo<'a>,
+        account: AccountInfo<'a>,
+        destination: AccountInfo<'a>,
+        authority: AccountInfo<'a>,
+        bump_seed: u8,
+    ) -> Result<(), ProgramError> {
+        let c_token_bytes = c_token.to_bytes();
+        let authority_signature_seeds = [&c_token_bytes[..32], &[bump_seed]];
+        let signers = &[&authority_signature_seeds[..]];
+        let ix = spl_token_2022::instruction::close_account(
+            token_program.key,
+            account.key,
+            destination.key,
+            authority.key,
+            &[],
+        )?;
+
+        invoke_signed_wrapper::<TokenError>(
+            &ix,
+            &[account, destination, authority, token_program],
+            signers,
+        )
+    }
+
+    /// Creates a rent exempt program derived account owned by the program.
+    /// `signer_seeds` must include the bump seed.
+    pub fn create_pda_account<'a>(
+        program_id: &Pubkey,
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let rent = Rent::get()?;
+        let lamports = rent.minimum_balance(space);
+
+        if new_account.lamports() == 0 {
+            return invoke_signed(
+                &system_instruction::create_account(
+                    payer.key,
+                    new_account.key,
+                    lamports,
+                    space as u64,
+                    program_id,
+                ),
+                &[payer.clone(), new_account.clone(), system_program.clone()],
+                &[signer_seeds],
+            );
+        }
+
+        // the account may be prefunded by anyone, so create_account can't be used
+        let required_lamports = lamports.saturating_sub(new_account.lamports());
+        if required_lamports > 0 {
+            invoke(
+                &system_instruction::transfer(payer.key, new_account.key, required_lamports),
+                &[payer.clone(), new_account.clone(), system_program.clone()],
+            )?;
+        }
+        invoke_signed(
+            &system_instruction::allocate(new_account.key, space as u64),
+            &[new_account.clone(), system_program.clone()],
+            &[signer_seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(new_account.key, program_id),
+            &[new_account.clone(), system_program.clone()],
+            &[signer_seeds],
+        )?;
+        if !rent.is_exempt(new_account.lamports(), space) {
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+
+        Ok(())
+    }
+
     pub fn authority_id(
         program_id: &Pubkey,
         my_info: &Pubkey,
@@ -163,27 +406,64 @@ impl Processor {
             .or(Err(CTokenError::InvalidProgramAddress))
     }
 
-    pub fn process_initial_config(accounts: &[AccountInfo], fee: u64) -> ProgramResult {
+    pub fn process_initial_config(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        fee: u64,
+        seed: String,
+    ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
 
         let config_info = next_account_info(account_info_iter)?;
         let owner_info = next_account_info(account_info_iter)?;
         let authority_info = next_account_info(account_info_iter)?;
         let fee_collector_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
 
-        let config_account = Config::try_from_slice(&config_info.data.borrow())?;
-        if config_account.is_initialized {
+        if !owner_info.is_signer {
+            return Err(CTokenError::InvalidOwner.into());
+        }
+        if seed.len() > MAX_SEED_LEN {
+            return Err(CTokenError::InvalidInput.into());
+        }
+        let (config_address, bump_seed) = Config::find_address(program_id, owner_info.key, &seed);
+        if *config_info.key != config_address {
+            return Err(CTokenError::InvalidProgramAddress.into());
+        }
+        if config_info.owner == program_id {
             return Err(CTokenError::AlreadyInUse.into());
         }
 
+        Self::create_pda_account(
+            program_id,
+            payer_info,
+            config_info,
+            system_program_info,
+            Config::LEN,
+            &[
+                Config::SEED,
+                &owner_info.key.to_bytes(),
+                seed.as_bytes(),
+                &[bump_seed],
+            ],
+        )?;
+
         let config = Config {
-            is_initialized: true,
+            account_type: AccountType::Config,
+            version: Config::VERSION,
             owner: *owner_info.key,
             authority: *authority_info.key,
             fee,
             fee_collector: *fee_collector_info.key,
+            paused: false,
+            pending_owner: None,
+            pending_authority: None,
         };
         config.serialize(&mut *config_info.data.borrow_mut())?;
+        log::Event::ConfigChanged(log::ConfigChanged::new(config_info.key, &config)).emit();
+
+        msg!("Created config {} with seed {}", config_info.key, seed);
 
         Ok(())
     }
@@ -198,15 +478,16 @@ impl Processor {
             return Err(ProgramError::IncorrectProgramId);
         }
 
-        let mut config = Config::try_from_slice(&config_info.data.borrow())?;
+        let mut config = Config::unpack(&config_info.data.borrow())?;
 
         if !owner_info.is_signer || *owner_info.key != config.owner {
             return Err(CTokenError::InvalidOwner.into());
         }
-        config.owner = *new_owner_info.key;
+        config.pending_owner = Some(*new_owner_info.key);
         config.serialize(&mut *config_info.data.borrow_mut())?;
+        log::Event::ConfigChanged(log::ConfigChanged::new(config_info.key, &config)).emit();
 
-        msg!("Owner change to {}", new_owner_info.key);
+        msg!("Owner change proposed to {}", new_owner_info.key);
 
         Ok(())
     }
@@ -224,19 +505,125 @@ impl Processor {
             return Err(ProgramError::IncorrectProgramId);
         }
 
-        let mut config = Config::try_from_slice(&config_info.data.borrow())?;
+        let mut config = Config::unpack(&config_info.data.borrow())?;
 
         if !owner_info.is_signer || *owner_info.key != config.owner {
             return Err(CTokenError::InvalidOwner.into());
         }
+        config.pending_authority = Some(*new_authority_info.key);
+        config.serialize(&mut *config_info.data.borrow_mut())?;
+        log::Event::ConfigChanged(log::ConfigChanged::new(config_info.key, &config)).emit();
+
+        msg!("Authority change proposed to {}", new_authority_info.key);
+
+        Ok(())
+    }
+
+    pub fn process_accept_owner(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let config_info = next_account_info(account_info_iter)?;
+        let new_owner_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut config = Config::unpack(&config_info.data.borrow())?;
+
+        if !new_owner_info.is_signer || Some(*new_owner_info.key) != config.pending_owner {
+            return Err(CTokenError::InvalidOwner.into());
+        }
+        config.owner = *new_owner_info.key;
+        config.pending_owner = None;
+        config.serialize(&mut *config_info.data.borrow_mut())?;
+        log::Event::ConfigChanged(log::ConfigChanged::new(config_info.key, &config)).emit();
+
+        msg!("Owner change to {}", new_owner_info.key);
+
+        Ok(())
+    }
+
+    pub fn process_accept_authority(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let config_info = next_account_info(account_info_iter)?;
+        let new_authority_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut config = Config::unpack(&config_info.data.borrow())?;
+
+        if !new_authority_info.is_signer
+            || Some(*new_authority_info.key) != config.pending_authority
+        {
+            return Err(CTokenError::InvalidAuthority.into());
+        }
         config.authority = *new_authority_info.key;
+        config.pending_authority = None;
         config.serialize(&mut *config_info.data.borrow_mut())?;
+        log::Event::ConfigChanged(log::ConfigChanged::new(config_info.key, &config)).emit();
 
         msg!("Authority change to {}", new_authority_info.key);
 
         Ok(())
     }
 
+    pub fn process_cancel_pending_owner(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let config_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut config = Config::unpack(&config_info.data.borrow())?;
+
+        if !owner_info.is_signer || *owner_info.key != config.owner {
+            return Err(CTokenError::InvalidOwner.into());
+        }
+        if let Some(pending_owner) = config.pending_owner.take() {
+            msg!("Owner change to {} cancelled", pending_owner);
+        }
+        config.serialize(&mut *config_info.data.borrow_mut())?;
+        log::Event::ConfigChanged(log::ConfigChanged::new(config_info.key, &config)).emit();
+
+        Ok(())
+    }
+
+    pub fn process_cancel_pending_authority(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let config_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut config = Config::unpack(&config_info.data.borrow())?;
+
+        if !owner_info.is_signer || *owner_info.key != config.owner {
+            return Err(CTokenError::InvalidOwner.into());
+        }
+        if let Some(pending_authority) = config.pending_authority.take() {
+            msg!("Authority change to {} cancelled", pending_authority);
+        }
+        config.serialize(&mut *config_info.data.borrow_mut())?;
+        log::Event::ConfigChanged(log::ConfigChanged::new(config_info.key, &config)).emit();
+
+        Ok(())
+    }
+
     pub fn process_change_fee(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
@@ -250,7 +637,7 @@ impl Processor {
             return Err(ProgramError::IncorrectProgramId);
         }
 
-        let mut config = Config::try_from_slice(&config_info.data.borrow())?;
+        let mut config = Config::unpack(&config_info.data.borrow())?;
 
         if !owner_info.is_signer || *owner_info.key != config.owner {
             return Err(CTokenError::InvalidOwner.into());
@@ -261,6 +648,7 @@ impl Processor {
             config.fee_collector = *fee_collector.key;
         }
         config.serialize(&mut *config_info.data.borrow_mut())?;
+        log::Event::ConfigChanged(log::ConfigChanged::new(config_info.key, &config)).emit();
 
         msg!("Fee change to {}", fee);
 
@@ -289,94 +677,484 @@ impl Processor {
             return Err(ProgramError::IncorrectProgramId);
         }
 
-        let config = Config::try_from_slice(&config_info.data.borrow())?;
+        let config = Config::unpack(&config_info.data.borrow())?;
 
         if !owner_info.is_signer || *owner_info.key != config.owner {
             return Err(CTokenError::InvalidOwner.into());
         }
 
-        let mut c_token = CToken::try_from_slice(&c_token_info.data.borrow())?;
+        let mut c_token = CToken::unpack(&c_token_info.data.borrow())?;
         if c_token.config != *config_info.key {
             return Err(CTokenError::InvalidConfig.into());
         }
         c_token.max = max;
         c_token.min = min;
         c_token.serialize(&mut *c_token_info.data.borrow_mut())?;
+        log::Event::LimitChanged(log::LimitChanged::new(c_token_info.key, &c_token)).emit();
 
         msg!("cToken bridge limit change to {} - {}", max, min);
 
         Ok(())
     }
 
-    pub fn process_create(
+    pub fn process_change_volume_limit(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        destination: u32,
-        max: u64,
-        min: u64,
+        window: i64,
+        outbound: u64,
+        inbound: u64,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
 
-        let c_token_info = next_account_info(account_info_iter)?;
-        let authority_info = next_account_info(account_info_iter)?;
-        let token_mint_info = next_account_info(account_info_iter)?;
-        let token_info = next_account_info(account_info_iter)?;
-        let token_program_info = next_account_info(account_info_iter)?;
-        let owner_info = next_account_info(account_info_iter)?;
-        let config_info = next_account_info(account_info_iter)?;
-
-        if min > max {
+        if window < 0 || (window == 0 && (outbound > 0 || inbound > 0)) {
             return Err(CTokenError::InvalidInput.into());
         }
 
-        let config = Config::try_from_slice(&config_info.data.borrow())?;
+        let config_info = next_account_info(account_info_iter)?;
+        let c_token_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
         if config_info.owner != program_id {
             return Err(ProgramError::IncorrectProgramId);
         }
-        if !owner_info.is_signer || *owner_info.key != config.owner {
-            return Err(CTokenError::InvalidOwner.into());
+        if c_token_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
         }
 
-        let token_program_id = *token_program_info.key;
-        let c_token_account = CToken::try_from_slice(&c_token_info.data.borrow())?;
-        if c_token_account.is_initialized {
-            return Err(CTokenError::AlreadyInUse.into());
-        }
+        let config = Config::unpack(&config_info.data.borrow())?;
 
-        let (c_token_authority, bump_seed) =
-            Pubkey::find_program_address(&[&c_token_info.key.to_bytes()], program_id);
-        if *authority_info.key != c_token_authority {
-            return Err(CTokenError::InvalidProgramAddress.into());
+        if !owner_info.is_signer || *owner_info.key != config.owner {
+            return Err(CTokenError::InvalidOwner.into());
         }
 
-        if destination != 0 {
-            let token = Self::unpack_token_account(token_info, &token_program_id)?;
-            if *authority_info.key != token.owner {
-                return Err(CTokenError::InvalidToken.into());
-            }
-            if *token_mint_info.key != token.mint {
-                return Err(CTokenError::InvalidToken.into());
-            }
+        let mut c_token = CToken::unpack(&c_token_info.data.borrow())?;
+        if c_token.config != *config_info.key {
+            return Err(CTokenError::InvalidConfig.into());
+        }
+        if c_token.window != window {
+            // volumes recorded for the old window are not comparable
+            c_token.window = window;
+            c_token.outbound = VolumeLimit::new(outbound);
+            c_token.inbound = VolumeLimit::new(inbound);
         } else {
-            let token_mint = Self::unpack_mint(token_mint_info, &token_program_id)?;
-            if COption::Some(*authority_info.key) != token_mint.mint_authority {
-                return Err(CTokenError::InvalidOwner.into());
-            }
+            c_token.outbound.cap = outbound;
+            c_token.inbound.cap = inbound;
         }
-
-        let c_token = CToken {
-            is_initialized: true,
-            bump_seed,
-            token_program_id,
-            config: *config_info.key,
-            token: *token_info.key,
-            token_mint: *token_mint_info.key,
-            destination,
-            index: 0,
-            max,
-            min,
-        };
         c_token.serialize(&mut *c_token_info.data.borrow_mut())?;
+        log::Event::LimitChanged(log::LimitChanged::new(c_token_info.key, &c_token)).emit();
+
+        msg!(
+            "cToken volume limit change to {} out - {} in per {}s",
+            outbound,
+            inbound,
+            window
+        );
+
+        Ok(())
+    }
+
+    pub fn process_change_token_fee(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bps: u16,
+        min: u64,
+        max: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        if bps > CToken::MAX_FEE_BPS || (max > 0 && min > max) {
+            return Err(CTokenError::InvalidInput.into());
+        }
+
+        let config_info = next_account_info(account_info_iter)?;
+        let c_token_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if c_token_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let config = Config::unpack(&config_info.data.borrow())?;
+
+        if !owner_info.is_signer || *owner_info.key != config.owner {
+            return Err(CTokenError::InvalidOwner.into());
+        }
+
+        let mut c_token = CToken::unpack(&c_token_info.data.borrow())?;
+        if c_token.config != *config_info.key {
+            return Err(CTokenError::InvalidConfig.into());
+        }
+        c_token.fee_bps = bps;
+        c_token.min_fee = min;
+        c_token.max_fee = max;
+        if c_token.has_token_fee() && c_token.mode == CTokenMode::Native {
+            // SOL fees go straight to a rent exempt system account
+            let fee_token_info = next_account_info(account_info_iter)?;
+            if *fee_token_info.key == c_token.token
+                || *fee_token_info.owner != system_program::id()
+                || fee_token_info.lamports() < Rent::get()?.minimum_balance(0)
+            {
+                return Err(CTokenError::InvalidFeeCollector.into());
+            }
+            c_token.fee_token = *fee_token_info.key;
+        } else if c_token.has_token_fee() {
+            let fee_token_info = next_account_info(account_info_iter)?;
+            // fees in the vault would be indistinguishable from bridged funds
+            if *fee_token_info.key == c_token.token {
+                return Err(CTokenError::InvalidFeeCollector.into());
+            }
+            let fee_token = Self::unpack_token_account(fee_token_info, &c_token.token_program_id)?;
+            if fee_token.owner
+                != Self::authority_id(program_id, c_token_info.key, c_token.bump_seed)?
+            {
+                return Err(CTokenError::InvalidFeeCollector.into());
+            }
+            if fee_token.mint != c_token.token_mint {
+                return Err(CTokenError::InvalidMint.into());
+            }
+            c_token.fee_token = *fee_token_info.key;
+        }
+        c_token.serialize(&mut *c_token_info.data.borrow_mut())?;
+        log::Event::TokenFeeChanged(log::TokenFeeChanged {
+            c_token: *c_token_info.key,
+            bps,
+            min,
+            max,
+            fee_token: c_token.fee_token,
+        })
+        .emit();
+
+        msg!("cToken token fee change to {} bps, {} - {}", bps, min, max);
+
+        Ok(())
+    }
+
+    pub fn process_withdraw_token_fee(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let config_info = next_account_info(account_info_iter)?;
+        let c_token_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        let token_authority_info = next_account_info(account_info_iter)?;
+        let fee_token_info = next_account_info(account_info_iter)?;
+        let destination_info = next_account_info(account_info_iter)?;
+        let token_mint_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if c_token_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let config = Config::unpack(&config_info.data.borrow())?;
+
+        if !owner_info.is_signer || *owner_info.key != config.owner {
+            return Err(CTokenError::InvalidOwner.into());
+        }
+
+        let c_token = CToken::unpack(&c_token_info.data.borrow())?;
+        if c_token.config != *config_info.key {
+            return Err(CTokenError::InvalidConfig.into());
+        }
+        if c_token.mode == CTokenMode::Native {
+            // native token fees are paid to the fee account directly
+            return Err(CTokenError::InvalidInput.into());
+        }
+        if c_token.fee_token != *fee_token_info.key {
+            return Err(CTokenError::InvalidFeeCollector.into());
+        }
+        if c_token.token_mint != *token_mint_info.key {
+            return Err(CTokenError::InvalidMint.into());
+        }
+        if token_program_info.key != &c_token.token_program_id {
+            return Err(CTokenError::InvalidInput.into());
+        }
+        if *token_authority_info.key
+            != Self::authority_id(program_id, c_token_info.key, c_token.bump_seed)?
+        {
+            return Err(CTokenError::InvalidProgramAddress.into());
+        }
+
+        let token_mint = Self::unpack_mint(token_mint_info, &c_token.token_program_id)?;
+        Self::token_transfer(
+            c_token_info.key,
+            token_program_info.clone(),
+            fee_token_info.clone(),
+            token_mint_info.clone(),
+            destination_info.clone(),
+            token_authority_info.clone(),
+            account_info_iter.as_slice(),
+            c_token.bump_seed,
+            amount,
+            token_mint.decimals,
+        )?;
+
+        log::Event::TokenFeeWithdrawn(log::TokenFeeWithdrawn {
+            c_token: *c_token_info.key,
+            destination: *destination_info.key,
+            amount,
+        })
+        .emit();
+        msg!(
+            "Withdraw token fee {} {} to {}",
+            amount,
+            &c_token.token_mint,
+            destination_info.key
+        );
+
+        Ok(())
+    }
+
+    pub fn process_set_paused(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        paused: bool,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let config_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut config = Config::unpack(&config_info.data.borrow())?;
+
+        if !owner_info.is_signer || *owner_info.key != config.owner {
+            return Err(CTokenError::InvalidOwner.into());
+        }
+
+        let state = if paused { "paused" } else { "unpaused" };
+        if let Ok(c_token_info) = next_account_info(account_info_iter) {
+            if c_token_info.owner != program_id {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            let mut c_token = CToken::unpack(&c_token_info.data.borrow())?;
+            if c_token.config != *config_info.key {
+                return Err(CTokenError::InvalidConfig.into());
+            }
+            if let Ok(route_info) = next_account_info(account_info_iter) {
+                if route_info.owner != program_id {
+                    return Err(ProgramError::IncorrectProgramId);
+                }
+                let mut route = Route::unpack(&route_info.data.borrow())?;
+                if route.c_token != *c_token_info.key {
+                    return Err(CTokenError::InvalidRoute.into());
+                }
+                route.paused = paused;
+                route.serialize(&mut *route_info.data.borrow_mut())?;
+                log::Event::PauseChanged(log::PauseChanged {
+                    account: *route_info.key,
+                    paused,
+                })
+                .emit();
+
+                msg!(
+                    "cToken {} route to {} {}",
+                    c_token_info.key,
+                    route.destination,
+                    state
+                );
+                return Ok(());
+            }
+            c_token.paused = paused;
+            c_token.serialize(&mut *c_token_info.data.borrow_mut())?;
+            log::Event::PauseChanged(log::PauseChanged {
+                account: *c_token_info.key,
+                paused,
+            })
+            .emit();
+
+            msg!("cToken {} {}", c_token_info.key, state);
+        } else {
+            config.paused = paused;
+            config.serialize(&mut *config_info.data.borrow_mut())?;
+            log::Event::PauseChanged(log::PauseChanged {
+                account: *config_info.key,
+                paused,
+            })
+            .emit();
+
+            msg!("Config {} {}", config_info.key, state);
+        }
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn process_create(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        destination: u32,
+        max: u64,
+        min: u64,
+        mode: CTokenMode,
+        remote_decimals: u8,
+        recipient_format: RecipientFormat,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let c_token_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let token_mint_info = next_account_info(account_info_iter)?;
+        let token_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        if min > max {
+            return Err(CTokenError::InvalidInput.into());
+        }
+
+        let config = Config::unpack(&config_info.data.borrow())?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if !owner_info.is_signer || *owner_info.key != config.owner {
+            return Err(CTokenError::InvalidOwner.into());
+        }
+
+        let token_program_id = *token_program_info.key;
+        let (c_token_address, c_token_bump_seed) = CToken::find_address(
+            program_id,
+            config_info.key,
+            token_mint_info.key,
+            destination,
+        );
+        if *c_token_info.key != c_token_address {
+            return Err(CTokenError::InvalidProgramAddress.into());
+        }
+        if c_token_info.owner == program_id {
+            return Err(CTokenError::AlreadyInUse.into());
+        }
+
+        let (c_token_authority, bump_seed) =
+            Pubkey::find_program_address(&[&c_token_info.key.to_bytes()], program_id);
+        if *authority_info.key != c_token_authority {
+            return Err(CTokenError::InvalidProgramAddress.into());
+        }
+
+        let decimals = if mode == CTokenMode::Native {
+            if *token_mint_info.key != NATIVE_MINT || token_program_id != system_program::id() {
+                return Err(CTokenError::InvalidMint.into());
+            }
+            spl_token_2022::native_mint::DECIMALS
+        } else {
+            let token_mint = Self::unpack_mint(token_mint_info, &token_program_id)?;
+            Self::check_mint_extensions(token_mint_info, authority_info.key)?;
+            token_mint.decimals
+        };
+        if remote_decimals.abs_diff(decimals) > CToken::MAX_DECIMALS_DIFF {
+            return Err(CTokenError::InvalidInput.into());
+        }
+        match mode {
+            CTokenMode::Lock => {
+                let token = Self::unpack_token_account(token_info, &token_program_id)?;
+                if *authority_info.key != token.owner {
+                    return Err(CTokenError::InvalidToken.into());
+                }
+                if *token_mint_info.key != token.mint {
+                    return Err(CTokenError::InvalidToken.into());
+                }
+                if token.delegate.is_some() || token.close_authority.is_some() {
+                    return Err(CTokenError::InvalidToken.into());
+                }
+            }
+            CTokenMode::Mint => {
+                let token_mint = Self::unpack_mint(token_mint_info, &token_program_id)?;
+                if COption::Some(*authority_info.key) != token_mint.mint_authority {
+                    return Err(CTokenError::InvalidOwner.into());
+                }
+            }
+            CTokenMode::Native => {
+                if token_info.key != authority_info.key {
+                    return Err(CTokenError::InvalidToken.into());
+                }
+                // keep the vault rent exempt, so settles can't close it
+                let required_lamports = Rent::get()?
+                    .minimum_balance(0)
+                    .saturating_sub(authority_info.lamports());
+                if required_lamports > 0 {
+                    invoke(
+                        &system_instruction::transfer(
+                            payer_info.key,
+                            authority_info.key,
+                            required_lamports,
+                        ),
+                        &[
+                            payer_info.clone(),
+                            authority_info.clone(),
+                            system_program_info.clone(),
+                        ],
+                    )?;
+                }
+            }
+        }
+
+        Self::create_pda_account(
+            program_id,
+            payer_info,
+            c_token_info,
+            system_program_info,
+            CToken::LEN,
+            &[
+                CToken::SEED,
+                &config_info.key.to_bytes(),
+                &token_mint_info.key.to_bytes(),
+                &destination.to_le_bytes(),
+                &[c_token_bump_seed],
+            ],
+        )?;
+
+        let c_token = CToken {
+            account_type: AccountType::CToken,
+            version: CToken::VERSION,
+            bump_seed,
+            token_program_id,
+            config: *config_info.key,
+            token: *token_info.key,
+            token_mint: *token_mint_info.key,
+            destination,
+            index: 0,
+            max,
+            min,
+            paused: false,
+            window: 0,
+            outbound: VolumeLimit::default(),
+            inbound: VolumeLimit::default(),
+            fee_bps: 0,
+            min_fee: 0,
+            max_fee: 0,
+            fee_token: Pubkey::default(),
+            liquidity: 0,
+            liquidity_reserve: 0,
+            mode,
+            decimals,
+            remote_decimals,
+            receipt_age: 0,
+            drop_off: 0,
+            drop_off_threshold: 0,
+            open_receipts: 0,
+            recipient_format,
+        };
+        c_token.serialize(&mut *c_token_info.data.borrow_mut())?;
+        log::Event::Created(log::Created {
+            config: *config_info.key,
+            c_token: *c_token_info.key,
+            token: *token_mint_info.key,
+            destination,
+            max,
+            min,
+        })
+        .emit();
 
         msg!(
             "Created cToken {} for token mint {}",
@@ -390,6 +1168,7 @@ impl Processor {
     pub fn process_bridge(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
+        destination: u32,
         amount: u64,
         recipient: String,
         payload: &[u8],
@@ -403,8 +1182,9 @@ impl Processor {
         let token_mint_info = next_account_info(account_info_iter)?;
         let token_program_info = next_account_info(account_info_iter)?;
         let config_info = next_account_info(account_info_iter)?;
+        let blocked_info = next_account_info(account_info_iter)?;
 
-        let config = Config::try_from_slice(&config_info.data.borrow())?;
+        let config = Config::unpack(&config_info.data.borrow())?;
         if config_info.owner != program_id {
             return Err(ProgramError::IncorrectProgramId);
         }
@@ -413,23 +1193,66 @@ impl Processor {
             return Err(ProgramError::IncorrectProgramId);
         }
 
-        let mut c_token = CToken::try_from_slice(&c_token_info.data.borrow())?;
+        let mut c_token = CToken::unpack(&c_token_info.data.borrow())?;
         if c_token.config != *config_info.key {
             return Err(CTokenError::InvalidConfig.into());
         }
+        if config.paused || c_token.paused {
+            return Err(CTokenError::Paused.into());
+        }
         if token_program_info.key != &c_token.token_program_id {
             return Err(CTokenError::InvalidInput.into());
         }
-        if amount > c_token.max || amount < c_token.min {
+        let wallet = Self::wallet_of(&c_token, user_info)?;
+        Self::check_not_blocked(program_id, config_info.key, &wallet, blocked_info)?;
+        // a delegate signing for the wallet must not be blocked either
+        if *user_transfer_authority_info.key != wallet {
+            let authority_blocked_info = next_account_info(account_info_iter)?;
+            Self::check_not_blocked(
+                program_id,
+                config_info.key,
+                user_transfer_authority_info.key,
+                authority_blocked_info,
+            )?;
+        }
+        let (max, min, fee, recipient_format) = if destination == c_token.destination {
+            (
+                c_token.max,
+                c_token.min,
+                config.fee,
+                c_token.recipient_format,
+            )
+        } else {
+            let route_info = next_account_info(account_info_iter)?;
+            if route_info.owner != program_id {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            let route = Route::unpack(&route_info.data.borrow())?;
+            if route.c_token != *c_token_info.key || route.destination != destination {
+                return Err(CTokenError::InvalidRoute.into());
+            }
+            if route.paused {
+                return Err(CTokenError::Paused.into());
+            }
+            (route.max, route.min, route.fee, route.recipient_format)
+        };
+        if amount > max || amount < min {
             return Err(CTokenError::InvalidAmount.into());
         }
+        let recipient = recipient_format.normalize(&recipient)?;
+        let token_fee = c_token.token_fee(amount)?;
+        let net_amount = amount - token_fee;
         if c_token.token_mint != *token_mint_info.key {
             return Err(CTokenError::InvalidMint.into());
         }
 
-        let token_mint = Self::unpack_mint(token_mint_info, &c_token.token_program_id)?;
+        let decimals = if c_token.mode == CTokenMode::Native {
+            spl_token_2022::native_mint::DECIMALS
+        } else {
+            Self::unpack_mint(token_mint_info, &c_token.token_program_id)?.decimals
+        };
 
-        if config.fee > 0 {
+        if fee > 0 {
             let payer = next_account_info(account_info_iter)?;
             let fee_collector = next_account_info(account_info_iter)?;
             if config.fee_collector != *fee_collector.key {
@@ -437,12 +1260,59 @@ impl Processor {
             }
 
             invoke(
-                &system_instruction::transfer(payer.key, fee_collector.key, config.fee),
+                &system_instruction::transfer(payer.key, fee_collector.key, fee),
                 &[payer.clone(), fee_collector.clone()],
             )?;
         }
 
-        if c_token.destination == 0 {
+        if token_fee > 0 {
+            let fee_token_info = next_account_info(account_info_iter)?;
+            if c_token.fee_token != *fee_token_info.key {
+                return Err(CTokenError::InvalidFeeCollector.into());
+            }
+            if c_token.mode == CTokenMode::Native {
+                invoke(
+                    &system_instruction::transfer(user_info.key, fee_token_info.key, token_fee),
+                    &[user_info.clone(), fee_token_info.clone()],
+                )?;
+            } else {
+                Self::token_transfer(
+                    c_token_info.key,
+                    token_program_info.clone(),
+                    user_info.clone(),
+                    token_mint_info.clone(),
+                    fee_token_info.clone(),
+                    user_transfer_authority_info.clone(),
+                    account_info_iter.as_slice(),
+                    c_token.bump_seed,
+                    token_fee,
+                    decimals,
+                )?;
+            }
+        }
+
+        let receipt_accounts = if c_token.receipt_age > 0 {
+            Some((
+                next_account_info(account_info_iter)?,
+                next_account_info(account_info_iter)?,
+                next_account_info(account_info_iter)?,
+            ))
+        } else {
+            None
+        };
+
+        // amount the cToken actually takes in, after any transfer fee of the mint
+        let received = if c_token.mode == CTokenMode::Native {
+            if c_token.token != *c_token_token_info.key {
+                return Err(CTokenError::InvalidToken.into());
+            }
+            // lock SOL
+            invoke(
+                &system_instruction::transfer(user_info.key, c_token_token_info.key, net_amount),
+                &[user_info.clone(), c_token_token_info.clone()],
+            )?;
+            net_amount
+        } else if c_token.mode == CTokenMode::Mint {
             // burn token
             Self::token_burn(
                 c_token_info.key,
@@ -451,13 +1321,19 @@ impl Processor {
                 token_mint_info.clone(),
                 user_transfer_authority_info.clone(),
                 c_token.bump_seed,
-                amount,
+                net_amount,
             )?;
+            net_amount
         } else {
             if user_info.key == c_token_token_info.key {
                 return Err(CTokenError::InvalidInput.into());
             }
+            if c_token.token != *c_token_token_info.key {
+                return Err(CTokenError::InvalidToken.into());
+            }
             // lock token
+            let vault_before =
+                Self::unpack_token_account(c_token_token_info, &c_token.token_program_id)?.amount;
             Self::token_transfer(
                 c_token_info.key,
                 token_program_info.clone(),
@@ -465,99 +1341,2047 @@ impl Processor {
                 token_mint_info.clone(),
                 c_token_token_info.clone(),
                 user_transfer_authority_info.clone(),
+                account_info_iter.as_slice(),
                 c_token.bump_seed,
-                amount,
-                token_mint.decimals,
+                net_amount,
+                decimals,
             )?;
+            Self::unpack_token_account(c_token_token_info, &c_token.token_program_id)?
+                .amount
+                .saturating_sub(vault_before)
+        };
+        if received == 0 {
+            return Err(CTokenError::InvalidAmount.into());
+        }
+        let remote_amount = c_token.to_remote_amount(received)?;
+        let clock = Clock::get()?;
+        c_token
+            .outbound
+            .consume(received, clock.unix_timestamp, c_token.window)?;
+        c_token.index += 1;
+        if receipt_accounts.is_some() {
+            c_token.open_receipts += 1;
         }
-        c_token.index = c_token.index + 1;
         c_token.serialize(&mut *c_token_info.data.borrow_mut())?;
 
+        if let Some((receipt_info, payer_info, system_program_info)) = receipt_accounts {
+            let (receipt, receipt_bump_seed) =
+                BridgeReceipt::find_address(program_id, c_token_info.key, c_token.index);
+            if *receipt_info.key != receipt {
+                return Err(CTokenError::InvalidProgramAddress.into());
+            }
+            Self::create_pda_account(
+                program_id,
+                payer_info,
+                receipt_info,
+                system_program_info,
+                BridgeReceipt::LEN,
+                &[
+                    BridgeReceipt::SEED,
+                    &c_token_info.key.to_bytes(),
+                    &c_token.index.to_le_bytes(),
+                    &[receipt_bump_seed],
+                ],
+            )?;
+            BridgeReceipt {
+                account_type: AccountType::BridgeReceipt,
+                c_token: *c_token_info.key,
+                index: c_token.index,
+                sender: *user_info.key,
+                recipient: recipient_address(&recipient)?,
+                amount: received,
+                fee,
+                token_fee,
+                destination,
+                payload_hash: keccak::hash(payload).to_bytes(),
+                slot: clock.slot,
+                bridged_at: clock.unix_timestamp,
+                payer: *payer_info.key,
+            }
+            .serialize(&mut *receipt_info.data.borrow_mut())?;
+        }
+
         let bridge_log = log::Bridge {
             token: c_token.token_mint,
             index: c_token.index,
             sender: *user_info.key,
             recipient,
-            amount,
-            fee: config.fee,
-            destination: c_token.destination,
+            amount: received,
+            fee,
+            destination,
             payload: payload.to_vec(),
+            token_fee,
+            remote_amount,
         };
+        #[cfg(feature = "legacy-bridge-log")]
         msg!("Bridge: {}", bridge_log);
+        log::Event::Bridge(bridge_log).emit();
 
         Ok(())
     }
 
-    pub fn process_settle(
+    /// Creates the associated token account of `wallet_info` for the cToken
+    /// mint unless it exists. The rent vault of the config pays the rent when
+    /// it holds enough, otherwise the payer does.
+    #[allow(clippy::too_many_arguments)]
+    fn create_recipient_account<'a>(
         program_id: &Pubkey,
-        accounts: &[AccountInfo],
-        amount: u64,
+        config: &Pubkey,
+        c_token: &CToken,
+        wallet_info: &AccountInfo<'a>,
+        account_info: &AccountInfo<'a>,
+        token_mint_info: &AccountInfo<'a>,
+        token_program_info: &AccountInfo<'a>,
+        associated_token_program_info: &AccountInfo<'a>,
+        rent_vault_info: &AccountInfo<'a>,
+        payer_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
     ) -> ProgramResult {
-        let account_info_iter = &mut accounts.iter();
-
-        let c_token_info = next_account_info(account_info_iter)?;
-        let token_authority_info = next_account_info(account_info_iter)?;
-        let c_token_token_info = next_account_info(account_info_iter)?;
-        let user_info = next_account_info(account_info_iter)?;
-        let authority_info = next_account_info(account_info_iter)?;
+        if *associated_token_program_info.key != spl_associated_token_account::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if *token_program_info.key != c_token.token_program_id {
+            return Err(CTokenError::IncorrectTokenProgramId.into());
+        }
+        if *account_info.key
+            != get_associated_token_address_with_program_id(
+                wallet_info.key,
+                &c_token.token_mint,
+                &c_token.token_program_id,
+            )
+        {
+            return Err(CTokenError::InvalidProgramAddress.into());
+        }
+        let (rent_vault, rent_vault_bump_seed) =
+            Config::find_rent_vault_address(program_id, config);
+        if *rent_vault_info.key != rent_vault {
+            return Err(CTokenError::InvalidProgramAddress.into());
+        }
+        if !account_info.data_is_empty() {
+            return Ok(());
+        }
+
+        // the associated token program adds the immutable owner extension
+        // to Token-2022 accounts
+        let space = if c_token.token_program_id == spl_token_2022::id() {
+            let mint_data = token_mint_info.data.borrow();
+            let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+            let mut extensions =
+                ExtensionType::get_required_init_account_extensions(&mint.get_extension_types()?);
+            extensions.push(ExtensionType::ImmutableOwner);
+            ExtensionType::try_calculate_account_len::<Account>(&extensions)?
+        } else {
+            Account::LEN
+        };
+        // the vault keeps its own rent exemption
+        let rent = Rent::get()?;
+        let vault_funded = rent_vault_info.lamports()
+            >= rent
+                .minimum_balance(space)
+                .saturating_add(rent.minimum_balance(0));
+        let funder_info = if vault_funded {
+            rent_vault_info
+        } else {
+            payer_info
+        };
+
+        let ix = create_associated_token_account_idempotent(
+            funder_info.key,
+            wallet_info.key,
+            &c_token.token_mint,
+            &c_token.token_program_id,
+        );
+        let account_infos = [
+            funder_info.clone(),
+            account_info.clone(),
+            wallet_info.clone(),
+            token_mint_info.clone(),
+            system_program_info.clone(),
+            token_program_info.clone(),
+            associated_token_program_info.clone(),
+        ];
+        if vault_funded {
+            invoke_signed(
+                &ix,
+                &account_infos,
+                &[&[
+                    Config::RENT_VAULT_SEED,
+                    &config.to_bytes(),
+                    &[rent_vault_bump_seed],
+                ]],
+            )?;
+        } else {
+            invoke(&ix, &account_infos)?;
+        }
+        msg!(
+            "Created token account {} of {}",
+            account_info.key,
+            wallet_info.key
+        );
+
+        Ok(())
+    }
+
+    /// Writes the receipt of a settled transfer at its program address
+    fn create_settlement_receipt<'a>(
+        program_id: &Pubkey,
+        config: &Pubkey,
+        receipt_info: &AccountInfo<'a>,
+        payer_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        receipt_bump_seed: u8,
+        settlement_receipt: &SettlementReceipt,
+    ) -> ProgramResult {
+        let transfer_id = &settlement_receipt.transfer_id;
+        Self::create_pda_account(
+            program_id,
+            payer_info,
+            receipt_info,
+            system_program_info,
+            SettlementReceipt::LEN,
+            &[
+                SettlementReceipt::SEED,
+                &config.to_bytes(),
+                &transfer_id.source_chain.to_le_bytes(),
+                &transfer_id.cashier,
+                &transfer_id.index.to_le_bytes(),
+                &[receipt_bump_seed],
+            ],
+        )?;
+        settlement_receipt.serialize(&mut *receipt_info.data.borrow_mut())?;
+        Ok(())
+    }
+
+    /// Pays a settled `amount` to `recipient_info` out of the vault, the
+    /// mint or the token account of the cToken, depending on its mode
+    #[allow(clippy::too_many_arguments)]
+    fn settle_payout<'a>(
+        c_token_key: &Pubkey,
+        c_token: &CToken,
+        token_authority_info: &AccountInfo<'a>,
+        c_token_token_info: &AccountInfo<'a>,
+        token_mint_info: &AccountInfo<'a>,
+        token_program_info: &AccountInfo<'a>,
+        recipient_info: &AccountInfo<'a>,
+        additional_accounts: &[AccountInfo<'a>],
+        amount: u64,
+    ) -> ProgramResult {
+        if c_token.mode == CTokenMode::Native {
+            if c_token.token != *c_token_token_info.key {
+                return Err(CTokenError::InvalidToken.into());
+            }
+            let unlockable = c_token_token_info
+                .lamports()
+                .saturating_sub(Rent::get()?.minimum_balance(0));
+            if amount > unlockable {
+                return Err(CTokenError::InsufficientLiquidity.into());
+            }
+            Self::vault_transfer(
+                c_token_key,
+                c_token_token_info.clone(),
+                recipient_info.clone(),
+                c_token.bump_seed,
+                amount,
+            )
+        } else if c_token.mode == CTokenMode::Mint {
+            Self::token_mint_to(
+                c_token_key,
+                token_program_info.clone(),
+                token_mint_info.clone(),
+                recipient_info.clone(),
+                token_authority_info.clone(),
+                c_token.bump_seed,
+                amount,
+            )
+        } else {
+            let token_mint = Self::unpack_mint(token_mint_info, &c_token.token_program_id)?;
+            Self::token_transfer(
+                c_token_key,
+                token_program_info.clone(),
+                c_token_token_info.clone(),
+                token_mint_info.clone(),
+                recipient_info.clone(),
+                token_authority_info.clone(),
+                additional_accounts,
+                c_token.bump_seed,
+                amount,
+                token_mint.decimals,
+            )
+        }
+    }
+
+    pub fn process_settle(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        remote_amount: u128,
+        transfer_id: TransferId,
+    ) -> ProgramResult {
+        Self::settle(program_id, accounts, remote_amount, transfer_id, true)
+    }
+
+    /// Settles a transfer, paying the drop-off of the cToken when `drop_off`
+    /// is set and the cToken has one
+    fn settle(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        remote_amount: u128,
+        transfer_id: TransferId,
+        drop_off: bool,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let c_token_info = next_account_info(account_info_iter)?;
+        let token_authority_info = next_account_info(account_info_iter)?;
+        let c_token_token_info = next_account_info(account_info_iter)?;
+        let user_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let token_mint_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let receipt_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let blocked_info = next_account_info(account_info_iter)?;
+
+        if c_token_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let config = Config::unpack(&config_info.data.borrow())?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut c_token = CToken::unpack(&c_token_info.data.borrow())?;
+        if c_token.config != *config_info.key {
+            return Err(CTokenError::InvalidConfig.into());
+        }
+        if config.paused || c_token.paused {
+            return Err(CTokenError::Paused.into());
+        }
+
+        if !authority_info.is_signer || *authority_info.key != config.authority {
+            return Err(CTokenError::InvalidAuthority.into());
+        }
+
+        // a wallet recipient of a token cToken is paid to its associated
+        // token account, created on the way
+        let wallet_recipient =
+            c_token.mode != CTokenMode::Native && *user_info.owner == system_program::id();
+        let (wallet, recipient_info) = if wallet_recipient {
+            (*user_info.key, next_account_info(account_info_iter)?)
+        } else {
+            (Self::wallet_of(&c_token, user_info)?, user_info)
+        };
+        Self::check_not_blocked(program_id, config_info.key, &wallet, blocked_info)?;
+        if wallet_recipient {
+            let associated_token_program_info = next_account_info(account_info_iter)?;
+            let rent_vault_info = next_account_info(account_info_iter)?;
+            Self::create_recipient_account(
+                program_id,
+                config_info.key,
+                &c_token,
+                user_info,
+                recipient_info,
+                token_mint_info,
+                token_program_info,
+                associated_token_program_info,
+                rent_vault_info,
+                payer_info,
+                system_program_info,
+            )?;
+        }
+
+        let drop_off_accounts = if drop_off && c_token.drop_off > 0 {
+            let wallet_info = next_account_info(account_info_iter)?;
+            let drop_off_vault_info = next_account_info(account_info_iter)?;
+            if *wallet_info.key != wallet {
+                return Err(CTokenError::InvalidInput.into());
+            }
+            let (drop_off_vault, drop_off_vault_bump_seed) =
+                Config::find_drop_off_vault_address(program_id, config_info.key);
+            if *drop_off_vault_info.key != drop_off_vault {
+                return Err(CTokenError::InvalidProgramAddress.into());
+            }
+            Some((wallet_info, drop_off_vault_info, drop_off_vault_bump_seed))
+        } else {
+            None
+        };
+
+        if *token_authority_info.key
+            != Self::authority_id(program_id, c_token_info.key, c_token.bump_seed)?
+        {
+            return Err(CTokenError::InvalidProgramAddress.into());
+        }
+
+        let (receipt, receipt_bump_seed) =
+            SettlementReceipt::find_address(program_id, config_info.key, &transfer_id);
+        if *receipt_info.key != receipt {
+            return Err(CTokenError::InvalidProgramAddress.into());
+        }
+        if receipt_info.owner == program_id {
+            return Err(CTokenError::AlreadySettled.into());
+        }
+
+        // dust the mint can't represent stays behind on the source chain
+        let (amount, dust) = c_token.to_local_amount(remote_amount)?;
+        if amount == 0 {
+            return Err(CTokenError::InvalidAmount.into());
+        }
+        if dust > 0 {
+            msg!("Settle dust {} truncated", dust);
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        c_token.inbound.consume(amount, now, c_token.window)?;
+        c_token.serialize(&mut *c_token_info.data.borrow_mut())?;
+
+        let settlement_receipt = SettlementReceipt {
+            account_type: AccountType::SettlementReceipt,
+            c_token: *c_token_info.key,
+            transfer_id,
+            recipient: *recipient_info.key,
+            amount,
+            settled_at: now,
+        };
+        Self::create_settlement_receipt(
+            program_id,
+            config_info.key,
+            receipt_info,
+            payer_info,
+            system_program_info,
+            receipt_bump_seed,
+            &settlement_receipt,
+        )?;
+        Self::settle_payout(
+            c_token_info.key,
+            &c_token,
+            token_authority_info,
+            c_token_token_info,
+            token_mint_info,
+            token_program_info,
+            recipient_info,
+            account_info_iter.as_slice(),
+            amount,
+        )?;
+        log::Event::Settle(log::Settle {
+            token: c_token.token_mint,
+            c_token: *c_token_info.key,
+            transfer_id: settlement_receipt.transfer_id,
+            recipient: *recipient_info.key,
+            amount,
+        })
+        .emit();
+        msg!(
+            "Settle {} {} to {}",
+            amount,
+            &c_token.token_mint,
+            recipient_info.key,
+        );
+
+        if let Some((wallet_info, drop_off_vault_info, drop_off_vault_bump_seed)) =
+            drop_off_accounts
+        {
+            Self::pay_drop_off(
+                config_info.key,
+                c_token_info.key,
+                &c_token,
+                wallet_info,
+                drop_off_vault_info,
+                system_program_info,
+                drop_off_vault_bump_seed,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Pays the drop-off of a cToken to a recipient wallet holding less than
+    /// its threshold. A vault short of the drop-off skips it rather than
+    /// failing the settlement.
+    fn pay_drop_off<'a>(
+        config: &Pubkey,
+        c_token_key: &Pubkey,
+        c_token: &CToken,
+        wallet_info: &AccountInfo<'a>,
+        drop_off_vault_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        drop_off_vault_bump_seed: u8,
+    ) -> ProgramResult {
+        if wallet_info.lamports() >= c_token.drop_off_threshold {
+            return Ok(());
+        }
+        let available = drop_off_vault_info
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0));
+        if available < c_token.drop_off {
+            msg!("Drop-off vault holds {} lamports, skip drop-off", available);
+            return Ok(());
+        }
+
+        Self::config_vault_transfer(
+            config,
+            Config::DROP_OFF_VAULT_SEED,
+            drop_off_vault_bump_seed,
+            drop_off_vault_info,
+            wallet_info,
+            system_program_info,
+            c_token.drop_off,
+        )?;
+        log::Event::DroppedOff(log::DroppedOff {
+            c_token: *c_token_key,
+            wallet: *wallet_info.key,
+            amount: c_token.drop_off,
+        })
+        .emit();
+        msg!(
+            "Drop off {} lamports to {}",
+            c_token.drop_off,
+            wallet_info.key
+        );
+
+        Ok(())
+    }
+
+    /// Settles like `process_settle`, then invokes the receiver program
+    /// signed by the caller address of the cToken. A failing receiver fails
+    /// the settlement, which can still be settled with `Settle`.
+    pub fn process_settle_and_call(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        remote_amount: u128,
+        transfer_id: TransferId,
+        sender: Vec<u8>,
+        payload: Vec<u8>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let c_token_info = next_account_info(account_info_iter)?;
+        let _token_authority_info = next_account_info(account_info_iter)?;
+        let _c_token_token_info = next_account_info(account_info_iter)?;
+        let user_info = next_account_info(account_info_iter)?;
+        let _authority_info = next_account_info(account_info_iter)?;
+        let token_mint_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let _config_info = next_account_info(account_info_iter)?;
+        let _receipt_info = next_account_info(account_info_iter)?;
+        let _payer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let _blocked_info = next_account_info(account_info_iter)?;
+        let receiver_info = next_account_info(account_info_iter)?;
+        let caller_info = next_account_info(account_info_iter)?;
+        let additional_accounts = account_info_iter.as_slice();
+
+        if !receiver_info.executable
+            || receiver_info.key == program_id
+            || receiver_info.key == token_program_info.key
+            || receiver_info.key == system_program_info.key
+        {
+            return Err(CTokenError::InvalidReceiver.into());
+        }
+        let (caller, caller_bump_seed) =
+            receiver::find_caller_address(program_id, c_token_info.key);
+        if *caller_info.key != caller {
+            return Err(CTokenError::InvalidProgramAddress.into());
+        }
+        // the receiver accounts take the place of the associated token
+        // account accounts of a wallet recipient
+        let c_token = CToken::unpack(&c_token_info.data.borrow())?;
+        if c_token.mode != CTokenMode::Native && *user_info.owner == system_program::id() {
+            return Err(CTokenError::ExpectedAccount.into());
+        }
+        // the recipient of the source transfer picks the receiver, not the
+        // settling authority
+        let (recipient_owner, _) = receiver::find_recipient_owner_address(receiver_info.key);
+        let user_owner = if c_token.mode == CTokenMode::Native {
+            *user_info.key
+        } else {
+            Self::unpack_token_account(user_info, &c_token.token_program_id)?.owner
+        };
+        if user_owner != recipient_owner {
+            return Err(CTokenError::InvalidReceiver.into());
+        }
+
+        // the receiver gets what the recipient account received, which a
+        // Token-2022 transfer fee makes less than the settled amount
+        let balance_of = |c_token: &CToken| -> Result<u64, ProgramError> {
+            if c_token.mode == CTokenMode::Native {
+                Ok(user_info.lamports())
+            } else {
+                Ok(Self::unpack_token_account(user_info, &c_token.token_program_id)?.amount)
+            }
+        };
+        let balance = balance_of(&c_token)?;
+
+        Self::settle(
+            program_id,
+            accounts,
+            remote_amount,
+            transfer_id.clone(),
+            false,
+        )?;
+
+        let c_token = CToken::unpack(&c_token_info.data.borrow())?;
+        let amount = balance_of(&c_token)?
+            .checked_sub(balance)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let instruction = receiver::receive(
+            receiver_info.key,
+            caller_info.key,
+            c_token_info.key,
+            user_info.key,
+            token_mint_info.key,
+            &additional_accounts
+                .iter()
+                // signers of the outer transaction, like the authority and
+                // the payer, must not sign for the receiver
+                .map(|account| AccountMeta {
+                    pubkey: *account.key,
+                    is_signer: false,
+                    is_writable: account.is_writable,
+                })
+                .collect::<Vec<_>>(),
+            &receiver::Receive {
+                amount,
+                token_mint: c_token.token_mint,
+                transfer_id,
+                sender,
+                payload,
+            },
+        )?;
+
+        let mut account_infos = vec![
+            caller_info.clone(),
+            c_token_info.clone(),
+            user_info.clone(),
+            token_mint_info.clone(),
+        ];
+        account_infos.extend_from_slice(additional_accounts);
+        account_infos.push(receiver_info.clone());
+        msg!("Calling receiver {}", receiver_info.key);
+        invoke_signed(
+            &instruction,
+            &account_infos,
+            &[&[
+                receiver::CALLER_SEED,
+                &c_token_info.key.to_bytes(),
+                &[caller_bump_seed],
+            ]],
+        )
+    }
+
+    /// Settles several transfers of a cToken. Every entry is checked like a
+    /// `Settle` before any of them is paid, and one event covers the batch.
+    pub fn process_settle_batch(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        entries: Vec<SettleEntry>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let c_token_info = next_account_info(account_info_iter)?;
+        let token_authority_info = next_account_info(account_info_iter)?;
+        let c_token_token_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let token_mint_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let associated_token_program_info = next_account_info(account_info_iter)?;
+        let rent_vault_info = next_account_info(account_info_iter)?;
+        let receipt_infos = next_account_infos(account_info_iter, entries.len())?;
+        let recipients = entries
+            .iter()
+            .map(|entry| entry.recipient_index as usize + 1)
+            .max()
+            .ok_or(CTokenError::InvalidInput)?;
+        let recipient_infos = next_account_infos(account_info_iter, recipients * 3)?;
+        let additional_accounts = account_info_iter.as_slice();
+
+        if c_token_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let config = Config::unpack(&config_info.data.borrow())?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut c_token = CToken::unpack(&c_token_info.data.borrow())?;
+        if c_token.config != *config_info.key {
+            return Err(CTokenError::InvalidConfig.into());
+        }
+        if config.paused || c_token.paused {
+            return Err(CTokenError::Paused.into());
+        }
+
+        if !authority_info.is_signer || *authority_info.key != config.authority {
+            return Err(CTokenError::InvalidAuthority.into());
+        }
+        // each recipient is an (account, token account, blocked account)
+        // triple, the token account being the associated token account of a
+        // wallet recipient, or the recipient account itself
+        let mut wallet_recipients = vec![];
+        for recipient in recipient_infos.chunks(3) {
+            let wallet_recipient =
+                c_token.mode != CTokenMode::Native && *recipient[0].owner == system_program::id();
+            let wallet = if wallet_recipient {
+                wallet_recipients.push(recipient);
+                *recipient[0].key
+            } else {
+                if recipient[1].key != recipient[0].key {
+                    return Err(CTokenError::InvalidInput.into());
+                }
+                Self::wallet_of(&c_token, &recipient[0])?
+            };
+            Self::check_not_blocked(program_id, config_info.key, &wallet, &recipient[2])?;
+        }
+
+        if *token_authority_info.key
+            != Self::authority_id(program_id, c_token_info.key, c_token.bump_seed)?
+        {
+            return Err(CTokenError::InvalidProgramAddress.into());
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let mut receipts = Vec::with_capacity(entries.len());
+        let mut total: u64 = 0;
+        for (i, entry) in entries.into_iter().enumerate() {
+            let receipt_info = &receipt_infos[i];
+            let (receipt, receipt_bump_seed) =
+                SettlementReceipt::find_address(program_id, config_info.key, &entry.transfer_id);
+            if *receipt_info.key != receipt {
+                return Err(CTokenError::InvalidProgramAddress.into());
+            }
+            if receipt_info.owner == program_id
+                || receipt_infos[..i]
+                    .iter()
+                    .any(|settled| settled.key == receipt_info.key)
+            {
+                return Err(CTokenError::AlreadySettled.into());
+            }
+
+            let (amount, dust) = c_token.to_local_amount(entry.amount)?;
+            if amount == 0 {
+                return Err(CTokenError::InvalidAmount.into());
+            }
+            if dust > 0 {
+                msg!("Settle dust {} truncated", dust);
+            }
+            total = total
+                .checked_add(amount)
+                .ok_or(CTokenError::InvalidAmount)?;
+
+            let recipient_info = &recipient_infos[entry.recipient_index as usize * 3 + 1];
+            receipts.push((
+                receipt_bump_seed,
+                recipient_info,
+                SettlementReceipt {
+                    account_type: AccountType::SettlementReceipt,
+                    c_token: *c_token_info.key,
+                    transfer_id: entry.transfer_id,
+                    recipient: *recipient_info.key,
+                    amount,
+                    settled_at: now,
+                },
+            ));
+        }
+
+        c_token.inbound.consume(total, now, c_token.window)?;
+        c_token.serialize(&mut *c_token_info.data.borrow_mut())?;
+
+        for recipient in wallet_recipients {
+            Self::create_recipient_account(
+                program_id,
+                config_info.key,
+                &c_token,
+                &recipient[0],
+                &recipient[1],
+                token_mint_info,
+                token_program_info,
+                associated_token_program_info,
+                rent_vault_info,
+                payer_info,
+                system_program_info,
+            )?;
+        }
+
+        let mut transfers = Vec::with_capacity(receipts.len());
+        for ((receipt_bump_seed, recipient_info, settlement_receipt), receipt_info) in
+            receipts.into_iter().zip(receipt_infos)
+        {
+            Self::create_settlement_receipt(
+                program_id,
+                config_info.key,
+                receipt_info,
+                payer_info,
+                system_program_info,
+                receipt_bump_seed,
+                &settlement_receipt,
+            )?;
+            Self::settle_payout(
+                c_token_info.key,
+                &c_token,
+                token_authority_info,
+                c_token_token_info,
+                token_mint_info,
+                token_program_info,
+                recipient_info,
+                additional_accounts,
+                settlement_receipt.amount,
+            )?;
+            transfers.push(log::SettledTransfer {
+                transfer_id: settlement_receipt.transfer_id,
+                recipient: settlement_receipt.recipient,
+                amount: settlement_receipt.amount,
+            });
+        }
+        msg!(
+            "Settle batch of {} transfers, {} {}",
+            transfers.len(),
+            total,
+            &c_token.token_mint,
+        );
+        log::Event::SettleBatch(log::SettleBatch {
+            token: c_token.token_mint,
+            c_token: *c_token_info.key,
+            transfers,
+        })
+        .emit();
+
+        Ok(())
+    }
+
+    pub fn process_withdraw_rent_vault(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let config_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        let rent_vault_info = next_account_info(account_info_iter)?;
+        let destination_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let config = Config::unpack(&config_info.data.borrow())?;
+
+        if !owner_info.is_signer || *owner_info.key != config.owner {
+            return Err(CTokenError::InvalidOwner.into());
+        }
+
+        let (rent_vault, rent_vault_bump_seed) =
+            Config::find_rent_vault_address(program_id, config_info.key);
+        if *rent_vault_info.key != rent_vault {
+            return Err(CTokenError::InvalidProgramAddress.into());
+        }
+
+        Self::config_vault_transfer(
+            config_info.key,
+            Config::RENT_VAULT_SEED,
+            rent_vault_bump_seed,
+            rent_vault_info,
+            destination_info,
+            system_program_info,
+            amount,
+        )?;
+
+        log::Event::RentVaultWithdrawn(log::RentVaultWithdrawn {
+            config: *config_info.key,
+            destination: *destination_info.key,
+            amount,
+        })
+        .emit();
+        msg!(
+            "Withdraw {} lamports of rent vault to {}",
+            amount,
+            destination_info.key
+        );
+
+        Ok(())
+    }
+
+    pub fn process_change_drop_off(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+        threshold: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let config_info = next_account_info(account_info_iter)?;
+        let c_token_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if c_token_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let config = Config::unpack(&config_info.data.borrow())?;
+
+        if !owner_info.is_signer || *owner_info.key != config.owner {
+            return Err(CTokenError::InvalidOwner.into());
+        }
+
+        let mut c_token = CToken::unpack(&c_token_info.data.borrow())?;
+        if c_token.config != *config_info.key {
+            return Err(CTokenError::InvalidConfig.into());
+        }
+        if amount > 0 {
+            // native settlements already pay SOL
+            if c_token.mode == CTokenMode::Native {
+                return Err(CTokenError::InvalidInput.into());
+            }
+            // an empty wallet can only receive a rent exempt balance
+            if amount < Rent::get()?.minimum_balance(0) {
+                return Err(CTokenError::InvalidAmount.into());
+            }
+        }
+        c_token.drop_off = amount;
+        c_token.drop_off_threshold = threshold;
+        c_token.serialize(&mut *c_token_info.data.borrow_mut())?;
+        log::Event::DropOffChanged(log::DropOffChanged {
+            c_token: *c_token_info.key,
+            amount,
+            threshold,
+        })
+        .emit();
+
+        msg!(
+            "cToken drop-off change to {} below {} lamports",
+            amount,
+            threshold
+        );
+
+        Ok(())
+    }
+
+    pub fn process_fund_drop_off_vault(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let config_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        let drop_off_vault_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let config = Config::unpack(&config_info.data.borrow())?;
+
+        if !owner_info.is_signer || *owner_info.key != config.owner {
+            return Err(CTokenError::InvalidOwner.into());
+        }
+
+        let (drop_off_vault, _) = Config::find_drop_off_vault_address(program_id, config_info.key);
+        if *drop_off_vault_info.key != drop_off_vault {
+            return Err(CTokenError::InvalidProgramAddress.into());
+        }
+
+        invoke(
+            &system_instruction::transfer(owner_info.key, drop_off_vault_info.key, amount),
+            &[
+                owner_info.clone(),
+                drop_off_vault_info.clone(),
+                system_program_info.clone(),
+            ],
+        )?;
+
+        log::Event::DropOffVaultFunded(log::DropOffVault {
+            config: *config_info.key,
+            account: *owner_info.key,
+            amount,
+        })
+        .emit();
+        msg!("Fund drop-off vault with {} lamports", amount);
+
+        Ok(())
+    }
+
+    pub fn process_withdraw_drop_off_vault(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let config_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        let drop_off_vault_info = next_account_info(account_info_iter)?;
+        let destination_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let config = Config::unpack(&config_info.data.borrow())?;
+
+        if !owner_info.is_signer || *owner_info.key != config.owner {
+            return Err(CTokenError::InvalidOwner.into());
+        }
+
+        let (drop_off_vault, drop_off_vault_bump_seed) =
+            Config::find_drop_off_vault_address(program_id, config_info.key);
+        if *drop_off_vault_info.key != drop_off_vault {
+            return Err(CTokenError::InvalidProgramAddress.into());
+        }
+
+        Self::config_vault_transfer(
+            config_info.key,
+            Config::DROP_OFF_VAULT_SEED,
+            drop_off_vault_bump_seed,
+            drop_off_vault_info,
+            destination_info,
+            system_program_info,
+            amount,
+        )?;
+
+        log::Event::DropOffVaultWithdrawn(log::DropOffVault {
+            config: *config_info.key,
+            account: *destination_info.key,
+            amount,
+        })
+        .emit();
+        msg!(
+            "Withdraw {} lamports of drop-off vault to {}",
+            amount,
+            destination_info.key
+        );
+
+        Ok(())
+    }
+
+    /// Reallocates `account` to `space`, funding rent from `payer`
+    pub fn realloc_account<'a>(
+        payer: &AccountInfo<'a>,
+        account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+    ) -> ProgramResult {
+        let required_lamports = Rent::get()?
+            .minimum_balance(space)
+            .saturating_sub(account.lamports());
+        if required_lamports > 0 {
+            invoke(
+                &system_instruction::transfer(payer.key, account.key, required_lamports),
+                &[payer.clone(), account.clone(), system_program.clone()],
+            )?;
+        }
+        account.realloc(space, false)
+    }
+
+    /// Closes a program account, moving its rent to `receiver`
+    pub fn close_account(account: &AccountInfo, receiver: &AccountInfo) -> ProgramResult {
+        let lamports = account.lamports();
+        **account.lamports.borrow_mut() = 0;
+        **receiver.lamports.borrow_mut() = receiver
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        account.realloc(0, false)?;
+        account.assign(&system_program::id());
+
+        Ok(())
+    }
+
+    pub fn process_migrate(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        remote_decimals: u8,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let config_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let legacy_config = config_info.data.borrow().first() == Some(&(AccountType::Legacy as u8));
+        let config = if legacy_config {
+            Config::from(LegacyConfig::try_from_slice(&config_info.data.borrow())?)
+        } else {
+            Config::unpack(&config_info.data.borrow())?
+        };
+
+        if !owner_info.is_signer || *owner_info.key != config.owner {
+            return Err(CTokenError::InvalidOwner.into());
+        }
+
+        if legacy_config {
+            Self::realloc_account(payer_info, config_info, system_program_info, Config::LEN)?;
+            config.serialize(&mut *config_info.data.borrow_mut())?;
+            log::Event::Migrated(log::Migrated {
+                account: *config_info.key,
+                version: Config::VERSION,
+            })
+            .emit();
+
+            msg!(
+                "Config {} migrated to version {}",
+                config_info.key,
+                Config::VERSION
+            );
+        }
+
+        while let Ok(c_token_info) = next_account_info(account_info_iter) {
+            let token_mint_info = next_account_info(account_info_iter)?;
+            if c_token_info.owner != program_id {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            let legacy_c_token =
+                c_token_info.data.borrow().first() == Some(&(AccountType::Legacy as u8));
+            let outdated_version = c_token_info.data.borrow().get(1).copied().unwrap_or(0);
+            let mut c_token = if legacy_c_token {
+                CToken::from(LegacyCToken::try_from_slice(&c_token_info.data.borrow())?)
+            } else {
+                let version = c_token_info.data.borrow().get(1).copied();
+                if version == Some(CToken::VERSION) {
+                    // already in the current layout
+                    CToken::unpack(&c_token_info.data.borrow())?;
+                    continue;
+                }
+                // fields appended since the earlier version start zeroed
+                Self::realloc_account(payer_info, c_token_info, system_program_info, CToken::LEN)?;
+                let mut c_token = CToken::unpack_outdated(&c_token_info.data.borrow())?;
+                if c_token.version < CToken::MODE_VERSION {
+                    c_token.mode = CTokenMode::from_legacy_destination(c_token.destination);
+                }
+                if c_token.version < CToken::RECIPIENT_FORMAT_VERSION {
+                    c_token.recipient_format =
+                        RecipientFormat::for_destination(c_token.destination)
+                            .unwrap_or(RecipientFormat::Evm);
+                }
+                c_token
+            };
+            if c_token.config != *config_info.key {
+                return Err(CTokenError::InvalidConfig.into());
+            }
+            if legacy_c_token || outdated_version < CToken::DECIMALS_VERSION {
+                if c_token.token_mint != *token_mint_info.key {
+                    return Err(CTokenError::InvalidMint.into());
+                }
+                let decimals = Self::mint_decimals(&c_token, token_mint_info)?;
+                if remote_decimals.abs_diff(decimals) > CToken::MAX_DECIMALS_DIFF {
+                    return Err(CTokenError::InvalidInput.into());
+                }
+                c_token.decimals = decimals;
+                c_token.remote_decimals = remote_decimals;
+            }
+            c_token.version = CToken::VERSION;
+            Self::realloc_account(payer_info, c_token_info, system_program_info, CToken::LEN)?;
+            c_token.serialize(&mut *c_token_info.data.borrow_mut())?;
+            log::Event::Migrated(log::Migrated {
+                account: *c_token_info.key,
+                version: CToken::VERSION,
+            })
+            .emit();
+
+            msg!(
+                "cToken {} migrated to version {}",
+                c_token_info.key,
+                CToken::VERSION
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn process_close_c_token(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let config_info = next_account_info(account_info_iter)?;
+        let c_token_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        let token_authority_info = next_account_info(account_info_iter)?;
+        let c_token_token_info = next_account_info(account_info_iter)?;
+        let destination_info = next_account_info(account_info_iter)?;
+        let token_mint_info = next_account_info(account_info_iter)?;
+        let new_mint_authority_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let receiver_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if c_token_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let config = Config::unpack(&config_info.data.borrow())?;
+
+        if !owner_info.is_signer || *owner_info.key != config.owner {
+            return Err(CTokenError::InvalidOwner.into());
+        }
+
+        let c_token = CToken::unpack(&c_token_info.data.borrow())?;
+        if c_token.config != *config_info.key {
+            return Err(CTokenError::InvalidConfig.into());
+        }
+        if c_token.token_mint != *token_mint_info.key {
+            return Err(CTokenError::InvalidMint.into());
+        }
+        if token_program_info.key != &c_token.token_program_id {
+            return Err(CTokenError::InvalidInput.into());
+        }
+        if *token_authority_info.key
+            != Self::authority_id(program_id, c_token_info.key, c_token.bump_seed)?
+        {
+            return Err(CTokenError::InvalidProgramAddress.into());
+        }
+        if c_token_info.key == receiver_info.key {
+            return Err(CTokenError::InvalidInput.into());
+        }
+        // provided liquidity belongs to the providers, not the destination,
+        // and open receipts would collide with those of a recreated cToken
+        if c_token.liquidity > 0 || c_token.open_receipts > 0 {
+            return Err(CTokenError::NotEmpty.into());
+        }
+        // SOL fees are paid out directly, token fees wait in the fee token account
+        if c_token.mode != CTokenMode::Native && c_token.fee_token != Pubkey::default() {
+            let fee_token_info = next_account_info(account_info_iter)?;
+            if *fee_token_info.key != c_token.fee_token {
+                return Err(CTokenError::InvalidFeeCollector.into());
+            }
+            let fee_token = Self::unpack_token_account(fee_token_info, &c_token.token_program_id)?;
+            if fee_token.amount > 0 {
+                return Err(CTokenError::NotEmpty.into());
+            }
+        }
+
+        let mut closed = log::Closed {
+            c_token: *c_token_info.key,
+            destination: Pubkey::default(),
+            amount: 0,
+            mint_authority: Pubkey::default(),
+        };
+        if c_token.mode == CTokenMode::Native {
+            if c_token.token != *c_token_token_info.key {
+                return Err(CTokenError::InvalidToken.into());
+            }
+            // drain the vault, rent included
+            let amount = c_token_token_info.lamports();
+            Self::vault_transfer(
+                c_token_info.key,
+                c_token_token_info.clone(),
+                destination_info.clone(),
+                c_token.bump_seed,
+                amount,
+            )?;
+
+            closed.destination = *destination_info.key;
+            closed.amount = amount;
+            msg!(
+                "Vault balance {} lamports moved to {}",
+                amount,
+                destination_info.key
+            );
+        } else if c_token.mode == CTokenMode::Mint {
+            // hand over the mint authority
+            Self::token_set_mint_authority(
+                c_token_info.key,
+                token_program_info.clone(),
+                token_mint_info.clone(),
+                new_mint_authority_info.key,
+                token_authority_info.clone(),
+                c_token.bump_seed,
+            )?;
+
+            closed.mint_authority = *new_mint_authority_info.key;
+            msg!(
+                "Mint authority of {} changed to {}",
+                token_mint_info.key,
+                new_mint_authority_info.key
+            );
+        } else {
+            if c_token.token != *c_token_token_info.key {
+                return Err(CTokenError::InvalidToken.into());
+            }
+            // drain the vault
+            let vault = Self::unpack_token_account(c_token_token_info, &c_token.token_program_id)?;
+            if vault.amount > 0 {
+                let token_mint = Self::unpack_mint(token_mint_info, &c_token.token_program_id)?;
+                Self::token_transfer(
+                    c_token_info.key,
+                    token_program_info.clone(),
+                    c_token_token_info.clone(),
+                    token_mint_info.clone(),
+                    destination_info.clone(),
+                    token_authority_info.clone(),
+                    account_info_iter.as_slice(),
+                    c_token.bump_seed,
+                    vault.amount,
+                    token_mint.decimals,
+                )?;
+            }
+
+            // the vault rent goes with the cToken rent
+            Self::token_close_account(
+                c_token_info.key,
+                token_program_info.clone(),
+                c_token_token_info.clone(),
+                receiver_info.clone(),
+                token_authority_info.clone(),
+                c_token.bump_seed,
+            )?;
+
+            closed.destination = *destination_info.key;
+            closed.amount = vault.amount;
+            msg!(
+                "Vault balance {} {} moved to {}",
+                vault.amount,
+                &c_token.token_mint,
+                destination_info.key
+            );
+        }
+
+        Self::close_account(c_token_info, receiver_info)?;
+
+        log::Event::Closed(closed).emit();
+        msg!("Closed cToken {}", c_token_info.key);
+
+        Ok(())
+    }
+
+    pub fn process_set_liquidity_provider(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        enabled: bool,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let config_info = next_account_info(account_info_iter)?;
+        let c_token_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        let provider_info = next_account_info(account_info_iter)?;
+        let liquidity_provider_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if c_token_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let config = Config::unpack(&config_info.data.borrow())?;
+
+        if !owner_info.is_signer || *owner_info.key != config.owner {
+            return Err(CTokenError::InvalidOwner.into());
+        }
+
+        let c_token = CToken::unpack(&c_token_info.data.borrow())?;
+        if c_token.config != *config_info.key {
+            return Err(CTokenError::InvalidConfig.into());
+        }
+        if c_token.mode != CTokenMode::Lock {
+            return Err(CTokenError::InvalidInput.into());
+        }
+
+        let (liquidity_provider_address, bump_seed) =
+            LiquidityProvider::find_address(program_id, c_token_info.key, provider_info.key);
+        if *liquidity_provider_info.key != liquidity_provider_address {
+            return Err(CTokenError::InvalidProgramAddress.into());
+        }
+
+        let mut liquidity_provider = if liquidity_provider_info.owner == program_id {
+            LiquidityProvider::unpack(&liquidity_provider_info.data.borrow())?
+        } else {
+            Self::create_pda_account(
+                program_id,
+                payer_info,
+                liquidity_provider_info,
+                system_program_info,
+                LiquidityProvider::LEN,
+                &[
+                    LiquidityProvider::SEED,
+                    &c_token_info.key.to_bytes(),
+                    &provider_info.key.to_bytes(),
+                    &[bump_seed],
+                ],
+            )?;
+            LiquidityProvider {
+                account_type: AccountType::LiquidityProvider,
+                c_token: *c_token_info.key,
+                provider: *provider_info.key,
+                enabled,
+                amount: 0,
+            }
+        };
+        liquidity_provider.enabled = enabled;
+        liquidity_provider.serialize(&mut *liquidity_provider_info.data.borrow_mut())?;
+        log::Event::LiquidityProviderChanged(log::LiquidityProviderChanged {
+            c_token: *c_token_info.key,
+            provider: *provider_info.key,
+            enabled,
+        })
+        .emit();
+
+        msg!(
+            "Liquidity provider {} of cToken {} {}",
+            provider_info.key,
+            c_token_info.key,
+            if enabled { "enabled" } else { "disabled" }
+        );
+
+        Ok(())
+    }
+
+    pub fn process_add_liquidity(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let c_token_info = next_account_info(account_info_iter)?;
+        let liquidity_provider_info = next_account_info(account_info_iter)?;
+        let provider_info = next_account_info(account_info_iter)?;
+        let source_info = next_account_info(account_info_iter)?;
+        let c_token_token_info = next_account_info(account_info_iter)?;
+        let token_mint_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if c_token_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if liquidity_provider_info.owner != program_id {
+            return Err(CTokenError::LiquidityProviderNotAllowed.into());
+        }
+
+        let config = Config::unpack(&config_info.data.borrow())?;
+        let mut c_token = CToken::unpack(&c_token_info.data.borrow())?;
+        if c_token.config != *config_info.key {
+            return Err(CTokenError::InvalidConfig.into());
+        }
+        if config.paused || c_token.paused {
+            return Err(CTokenError::Paused.into());
+        }
+        if c_token.mode != CTokenMode::Lock || c_token.token != *c_token_token_info.key {
+            return Err(CTokenError::InvalidToken.into());
+        }
+        if c_token.token_mint != *token_mint_info.key {
+            return Err(CTokenError::InvalidMint.into());
+        }
+        if token_program_info.key != &c_token.token_program_id {
+            return Err(CTokenError::InvalidInput.into());
+        }
+
+        let mut liquidity_provider =
+            LiquidityProvider::unpack(&liquidity_provider_info.data.borrow())?;
+        if liquidity_provider.c_token != *c_token_info.key
+            || liquidity_provider.provider != *provider_info.key
+            || !liquidity_provider.enabled
+            || !provider_info.is_signer
+        {
+            return Err(CTokenError::LiquidityProviderNotAllowed.into());
+        }
+        if amount == 0 {
+            return Err(CTokenError::InvalidAmount.into());
+        }
+
+        let token_mint = Self::unpack_mint(token_mint_info, &c_token.token_program_id)?;
+        let vault_before =
+            Self::unpack_token_account(c_token_token_info, &c_token.token_program_id)?.amount;
+        Self::token_transfer(
+            c_token_info.key,
+            token_program_info.clone(),
+            source_info.clone(),
+            token_mint_info.clone(),
+            c_token_token_info.clone(),
+            provider_info.clone(),
+            account_info_iter.as_slice(),
+            c_token.bump_seed,
+            amount,
+            token_mint.decimals,
+        )?;
+        // credit what the vault received after any transfer fee
+        let received = Self::unpack_token_account(c_token_token_info, &c_token.token_program_id)?
+            .amount
+            .saturating_sub(vault_before);
+
+        liquidity_provider.amount = liquidity_provider
+            .amount
+            .checked_add(received)
+            .ok_or(CTokenError::InvalidAmount)?;
+        c_token.liquidity = c_token
+            .liquidity
+            .checked_add(received)
+            .ok_or(CTokenError::InvalidAmount)?;
+        liquidity_provider.serialize(&mut *liquidity_provider_info.data.borrow_mut())?;
+        c_token.serialize(&mut *c_token_info.data.borrow_mut())?;
+        log::Event::LiquidityAdded(log::Liquidity {
+            c_token: *c_token_info.key,
+            provider: *provider_info.key,
+            amount: received,
+            provided: liquidity_provider.amount,
+        })
+        .emit();
+
+        msg!(
+            "Add liquidity {} {} from {}",
+            received,
+            &c_token.token_mint,
+            provider_info.key
+        );
+
+        Ok(())
+    }
+
+    pub fn process_remove_liquidity(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let c_token_info = next_account_info(account_info_iter)?;
+        let liquidity_provider_info = next_account_info(account_info_iter)?;
+        let provider_info = next_account_info(account_info_iter)?;
+        let token_authority_info = next_account_info(account_info_iter)?;
+        let c_token_token_info = next_account_info(account_info_iter)?;
+        let destination_info = next_account_info(account_info_iter)?;
         let token_mint_info = next_account_info(account_info_iter)?;
         let token_program_info = next_account_info(account_info_iter)?;
         let config_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if c_token_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if liquidity_provider_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let config = Config::unpack(&config_info.data.borrow())?;
+        let mut c_token = CToken::unpack(&c_token_info.data.borrow())?;
+        if c_token.config != *config_info.key {
+            return Err(CTokenError::InvalidConfig.into());
+        }
+        if config.paused || c_token.paused {
+            return Err(CTokenError::Paused.into());
+        }
+        if c_token.mode != CTokenMode::Lock || c_token.token != *c_token_token_info.key {
+            return Err(CTokenError::InvalidToken.into());
+        }
+        if c_token.token_mint != *token_mint_info.key {
+            return Err(CTokenError::InvalidMint.into());
+        }
+        if token_program_info.key != &c_token.token_program_id {
+            return Err(CTokenError::InvalidInput.into());
+        }
+        if *token_authority_info.key
+            != Self::authority_id(program_id, c_token_info.key, c_token.bump_seed)?
+        {
+            return Err(CTokenError::InvalidProgramAddress.into());
+        }
+
+        let mut liquidity_provider =
+            LiquidityProvider::unpack(&liquidity_provider_info.data.borrow())?;
+        if liquidity_provider.c_token != *c_token_info.key
+            || liquidity_provider.provider != *provider_info.key
+            || !provider_info.is_signer
+        {
+            return Err(CTokenError::LiquidityProviderNotAllowed.into());
+        }
+        if amount == 0 || amount > liquidity_provider.amount {
+            return Err(CTokenError::InvalidAmount.into());
+        }
+
+        let vault = Self::unpack_token_account(c_token_token_info, &c_token.token_program_id)?;
+        if vault.amount < amount || vault.amount - amount < c_token.liquidity_reserve {
+            return Err(CTokenError::InsufficientLiquidity.into());
+        }
+
+        liquidity_provider.amount -= amount;
+        c_token.liquidity = c_token.liquidity.saturating_sub(amount);
+        liquidity_provider.serialize(&mut *liquidity_provider_info.data.borrow_mut())?;
+        c_token.serialize(&mut *c_token_info.data.borrow_mut())?;
+        log::Event::LiquidityRemoved(log::Liquidity {
+            c_token: *c_token_info.key,
+            provider: *provider_info.key,
+            amount,
+            provided: liquidity_provider.amount,
+        })
+        .emit();
+
+        let token_mint = Self::unpack_mint(token_mint_info, &c_token.token_program_id)?;
+        Self::token_transfer(
+            c_token_info.key,
+            token_program_info.clone(),
+            c_token_token_info.clone(),
+            token_mint_info.clone(),
+            destination_info.clone(),
+            token_authority_info.clone(),
+            account_info_iter.as_slice(),
+            c_token.bump_seed,
+            amount,
+            token_mint.decimals,
+        )?;
+
+        msg!(
+            "Remove liquidity {} {} to {}",
+            amount,
+            &c_token.token_mint,
+            destination_info.key
+        );
+
+        Ok(())
+    }
+
+    pub fn process_change_liquidity_reserve(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        reserve: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let config_info = next_account_info(account_info_iter)?;
+        let c_token_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if c_token_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let config = Config::unpack(&config_info.data.borrow())?;
+
+        if !owner_info.is_signer || *owner_info.key != config.owner {
+            return Err(CTokenError::InvalidOwner.into());
+        }
+
+        let mut c_token = CToken::unpack(&c_token_info.data.borrow())?;
+        if c_token.config != *config_info.key {
+            return Err(CTokenError::InvalidConfig.into());
+        }
+        c_token.liquidity_reserve = reserve;
+        c_token.serialize(&mut *c_token_info.data.borrow_mut())?;
+        log::Event::LiquidityReserveChanged(log::LiquidityReserveChanged {
+            c_token: *c_token_info.key,
+            reserve,
+        })
+        .emit();
+
+        msg!("cToken liquidity reserve change to {}", reserve);
+
+        Ok(())
+    }
+
+    pub fn process_change_decimals(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        remote_decimals: u8,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let config_info = next_account_info(account_info_iter)?;
+        let c_token_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        let token_mint_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if c_token_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let config = Config::unpack(&config_info.data.borrow())?;
+
+        if !owner_info.is_signer || *owner_info.key != config.owner {
+            return Err(CTokenError::InvalidOwner.into());
+        }
+
+        let mut c_token = CToken::unpack(&c_token_info.data.borrow())?;
+        if c_token.config != *config_info.key {
+            return Err(CTokenError::InvalidConfig.into());
+        }
+        if c_token.token_mint != *token_mint_info.key {
+            return Err(CTokenError::InvalidMint.into());
+        }
+        let decimals = Self::mint_decimals(&c_token, token_mint_info)?;
+        if remote_decimals.abs_diff(decimals) > CToken::MAX_DECIMALS_DIFF {
+            return Err(CTokenError::InvalidInput.into());
+        }
+        c_token.decimals = decimals;
+        c_token.remote_decimals = remote_decimals;
+        c_token.serialize(&mut *c_token_info.data.borrow_mut())?;
+        log::Event::DecimalsChanged(log::DecimalsChanged {
+            c_token: *c_token_info.key,
+            decimals,
+            remote_decimals,
+        })
+        .emit();
+
+        msg!(
+            "cToken decimals change to {}, remote {}",
+            decimals,
+            remote_decimals
+        );
+
+        Ok(())
+    }
+
+    pub fn process_change_receipt_age(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        age: i64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
 
+        let config_info = next_account_info(account_info_iter)?;
+        let c_token_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
         if c_token_info.owner != program_id {
             return Err(ProgramError::IncorrectProgramId);
         }
 
-        let config = Config::try_from_slice(&config_info.data.borrow())?;
-        if config_info.owner != program_id {
-            return Err(ProgramError::IncorrectProgramId);
+        let config = Config::unpack(&config_info.data.borrow())?;
+
+        if !owner_info.is_signer || *owner_info.key != config.owner {
+            return Err(CTokenError::InvalidOwner.into());
         }
 
-        let c_token = CToken::try_from_slice(&c_token_info.data.borrow())?;
+        let mut c_token = CToken::unpack(&c_token_info.data.borrow())?;
         if c_token.config != *config_info.key {
             return Err(CTokenError::InvalidConfig.into());
         }
+        if age < 0 {
+            return Err(CTokenError::InvalidInput.into());
+        }
+        c_token.receipt_age = age;
+        c_token.serialize(&mut *c_token_info.data.borrow_mut())?;
+        log::Event::ReceiptAgeChanged(log::ReceiptAgeChanged {
+            c_token: *c_token_info.key,
+            age,
+        })
+        .emit();
 
-        if !authority_info.is_signer || *authority_info.key != config.authority {
-            return Err(CTokenError::InvalidAuthority.into());
+        msg!("cToken receipt age change to {}", age);
+
+        Ok(())
+    }
+
+    /// Closes a bridge receipt once it is older than the receipt age of its
+    /// cToken, or once the cToken is closed
+    pub fn process_close_bridge_receipt(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let c_token_info = next_account_info(account_info_iter)?;
+        let receipt_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        if receipt_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
         }
 
-        if *token_authority_info.key
-            != Self::authority_id(program_id, c_token_info.key, c_token.bump_seed)?
-        {
+        let receipt = BridgeReceipt::unpack(&receipt_info.data.borrow())?;
+        if receipt.c_token != *c_token_info.key {
+            return Err(CTokenError::InvalidInput.into());
+        }
+        if receipt.payer != *payer_info.key {
+            return Err(CTokenError::InvalidInput.into());
+        }
+        if c_token_info.owner == program_id {
+            let mut c_token = CToken::unpack(&c_token_info.data.borrow())?;
+            let now = Clock::get()?.unix_timestamp;
+            if now < receipt.bridged_at.saturating_add(c_token.receipt_age) {
+                return Err(CTokenError::ReceiptTooRecent.into());
+            }
+            c_token.open_receipts = c_token.open_receipts.saturating_sub(1);
+            c_token.serialize(&mut *c_token_info.data.borrow_mut())?;
+        }
+
+        let rent = receipt_info.lamports();
+        Self::close_account(receipt_info, payer_info)?;
+
+        log::Event::BridgeReceiptClosed(log::BridgeReceiptClosed {
+            c_token: *c_token_info.key,
+            receipt: *receipt_info.key,
+            index: receipt.index,
+            rent_recipient: *payer_info.key,
+            rent,
+        })
+        .emit();
+        msg!("Bridge receipt {} closed", receipt.index);
+
+        Ok(())
+    }
+
+    pub fn process_block(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        account: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let config_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        let blocked_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let config = Config::unpack(&config_info.data.borrow())?;
+
+        if !owner_info.is_signer || *owner_info.key != config.owner {
+            return Err(CTokenError::InvalidOwner.into());
+        }
+
+        let (blocked_address, bump_seed) =
+            BlockedAccount::find_address(program_id, config_info.key, account);
+        if *blocked_info.key != blocked_address {
             return Err(CTokenError::InvalidProgramAddress.into());
         }
+        if blocked_info.owner == program_id {
+            return Err(CTokenError::AlreadyInUse.into());
+        }
 
-        let token_mint = Self::unpack_mint(token_mint_info, &c_token.token_program_id)?;
+        Self::create_pda_account(
+            program_id,
+            payer_info,
+            blocked_info,
+            system_program_info,
+            BlockedAccount::LEN,
+            &[
+                BlockedAccount::SEED,
+                &config_info.key.to_bytes(),
+                &account.to_bytes(),
+                &[bump_seed],
+            ],
+        )?;
+        let blocked = BlockedAccount {
+            account_type: AccountType::BlockedAccount,
+            config: *config_info.key,
+            account: *account,
+        };
+        blocked.serialize(&mut *blocked_info.data.borrow_mut())?;
+        log::Event::BlockChanged(log::BlockChanged {
+            config: *config_info.key,
+            account: *account,
+            blocked: true,
+        })
+        .emit();
 
-        if c_token.destination == 0 {
-            Self::token_mint_to(
-                c_token_info.key,
-                token_program_info.clone(),
-                token_mint_info.clone(),
-                user_info.clone(),
-                token_authority_info.clone(),
-                c_token.bump_seed,
-                amount,
-            )?;
+        msg!("Account {} blocked", account);
+
+        Ok(())
+    }
+
+    pub fn process_unblock(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let config_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        let blocked_info = next_account_info(account_info_iter)?;
+        let receiver_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if blocked_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let config = Config::unpack(&config_info.data.borrow())?;
+
+        if !owner_info.is_signer || *owner_info.key != config.owner {
+            return Err(CTokenError::InvalidOwner.into());
+        }
+
+        let blocked = BlockedAccount::unpack(&blocked_info.data.borrow())?;
+        if blocked.config != *config_info.key {
+            return Err(CTokenError::InvalidConfig.into());
+        }
+        if blocked_info.key == receiver_info.key {
+            return Err(CTokenError::InvalidInput.into());
+        }
+
+        Self::close_account(blocked_info, receiver_info)?;
+        log::Event::BlockChanged(log::BlockChanged {
+            config: blocked.config,
+            account: blocked.account,
+            blocked: false,
+        })
+        .emit();
+
+        msg!("Account {} unblocked", blocked.account);
+
+        Ok(())
+    }
+
+    pub fn process_add_route(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        destination: u32,
+        max: u64,
+        min: u64,
+        fee: u64,
+        recipient_format: RecipientFormat,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let config_info = next_account_info(account_info_iter)?;
+        let c_token_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        let route_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if c_token_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if min > max {
+            return Err(CTokenError::InvalidInput.into());
+        }
+
+        let config = Config::unpack(&config_info.data.borrow())?;
+
+        if !owner_info.is_signer || *owner_info.key != config.owner {
+            return Err(CTokenError::InvalidOwner.into());
+        }
+
+        let c_token = CToken::unpack(&c_token_info.data.borrow())?;
+        if c_token.config != *config_info.key {
+            return Err(CTokenError::InvalidConfig.into());
+        }
+        if destination == c_token.destination {
+            return Err(CTokenError::InvalidRoute.into());
+        }
+
+        let (route_address, bump_seed) =
+            Route::find_address(program_id, c_token_info.key, destination);
+        if *route_info.key != route_address {
+            return Err(CTokenError::InvalidProgramAddress.into());
+        }
+
+        let paused = if route_info.owner == program_id {
+            Route::unpack(&route_info.data.borrow())?.paused
         } else {
-            Self::token_transfer(
-                c_token_info.key,
-                token_program_info.clone(),
-                c_token_token_info.clone(),
-                token_mint_info.clone(),
-                user_info.clone(),
-                token_authority_info.clone(),
-                c_token.bump_seed,
-                amount,
-                token_mint.decimals,
+            Self::create_pda_account(
+                program_id,
+                payer_info,
+                route_info,
+                system_program_info,
+                Route::LEN,
+                &[
+                    Route::SEED,
+                    &c_token_info.key.to_bytes(),
+                    &destination.to_le_bytes(),
+                    &[bump_seed],
+                ],
             )?;
+            false
+        };
+        let route = Route {
+            account_type: AccountType::Route,
+            c_token: *c_token_info.key,
+            destination,
+            max,
+            min,
+            fee,
+            paused,
+            recipient_format,
+        };
+        route.serialize(&mut *route_info.data.borrow_mut())?;
+        log::Event::RouteChanged(log::RouteChanged {
+            c_token: *c_token_info.key,
+            destination,
+            max,
+            min,
+            fee,
+            enabled: true,
+        })
+        .emit();
+
+        msg!(
+            "cToken {} route to {} set to {} - {}, fee {}",
+            c_token_info.key,
+            destination,
+            max,
+            min,
+            fee
+        );
+
+        Ok(())
+    }
+
+    pub fn process_remove_route(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let config_info = next_account_info(account_info_iter)?;
+        let c_token_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        let route_info = next_account_info(account_info_iter)?;
+        let receiver_info = next_account_info(account_info_iter)?;
+        if config_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if c_token_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if route_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let config = Config::unpack(&config_info.data.borrow())?;
+
+        if !owner_info.is_signer || *owner_info.key != config.owner {
+            return Err(CTokenError::InvalidOwner.into());
+        }
+
+        let c_token = CToken::unpack(&c_token_info.data.borrow())?;
+        if c_token.config != *config_info.key {
+            return Err(CTokenError::InvalidConfig.into());
+        }
+        let route = Route::unpack(&route_info.data.borrow())?;
+        if route.c_token != *c_token_info.key {
+            return Err(CTokenError::InvalidRoute.into());
         }
+        if route_info.key == receiver_info.key {
+            return Err(CTokenError::InvalidInput.into());
+        }
+
+        Self::close_account(route_info, receiver_info)?;
+        log::Event::RouteChanged(log::RouteChanged {
+            c_token: route.c_token,
+            destination: route.destination,
+            max: route.max,
+            min: route.min,
+            fee: route.fee,
+            enabled: false,
+        })
+        .emit();
+
         msg!(
-            "Settle {} {} to {}",
-            amount,
-            &c_token.token_mint,
-            user_info.key,
+            "cToken {} route to {} removed",
+            c_token_info.key,
+            route.destination
         );
 
         Ok(())
@@ -569,7 +3393,9 @@ impl Processor {
             .map_err(|_| ProgramError::InvalidInstructionData)?;
 
         match instruction {
-            CTokenInstruction::Config { fee } => Processor::process_initial_config(accounts, fee),
+            CTokenInstruction::Config { fee, seed } => {
+                Processor::process_initial_config(program_id, accounts, fee, seed)
+            }
             CTokenInstruction::TransferOwner => {
                 Processor::process_transfer_owner(program_id, accounts)
             }
@@ -583,17 +3409,140 @@ impl Processor {
                 destination,
                 max,
                 min,
-            } => Processor::process_create(program_id, accounts, destination, max, min),
+                mode,
+                remote_decimals,
+                recipient_format,
+            } => Processor::process_create(
+                program_id,
+                accounts,
+                destination,
+                max,
+                min,
+                mode,
+                remote_decimals,
+                recipient_format,
+            ),
             CTokenInstruction::ChangeLimit { max, min } => {
                 Processor::process_change_limit(program_id, accounts, max, min)
             }
             CTokenInstruction::Bridge {
+                destination,
+                amount,
+                recipient,
+                payload,
+            } => Processor::process_bridge(
+                program_id,
+                accounts,
+                destination,
                 amount,
                 recipient,
+                &payload,
+            ),
+            CTokenInstruction::Settle {
+                amount,
+                transfer_id,
+            } => Processor::process_settle(program_id, accounts, amount, transfer_id),
+            CTokenInstruction::ChangeReceiptAge { age } => {
+                Processor::process_change_receipt_age(program_id, accounts, age)
+            }
+            CTokenInstruction::WithdrawRentVault { amount } => {
+                Processor::process_withdraw_rent_vault(program_id, accounts, amount)
+            }
+            CTokenInstruction::ChangeDropOff { amount, threshold } => {
+                Processor::process_change_drop_off(program_id, accounts, amount, threshold)
+            }
+            CTokenInstruction::FundDropOffVault { amount } => {
+                Processor::process_fund_drop_off_vault(program_id, accounts, amount)
+            }
+            CTokenInstruction::WithdrawDropOffVault { amount } => {
+                Processor::process_withdraw_drop_off_vault(program_id, accounts, amount)
+            }
+            CTokenInstruction::SettleBatch { entries } => {
+                Processor::process_settle_batch(program_id, accounts, entries)
+            }
+            CTokenInstruction::CloseBridgeReceipt => {
+                Processor::process_close_bridge_receipt(program_id, accounts)
+            }
+            CTokenInstruction::SettleAndCall {
+                amount,
+                transfer_id,
+                sender,
+                payload,
+            } => Processor::process_settle_and_call(
+                program_id,
+                accounts,
+                amount,
+                transfer_id,
+                sender,
                 payload,
-            } => Processor::process_bridge(program_id, accounts, amount, recipient, &payload),
-            CTokenInstruction::Settle { amount } => {
-                Processor::process_settle(program_id, accounts, amount)
+            ),
+            CTokenInstruction::ChangeVolumeLimit {
+                window,
+                outbound,
+                inbound,
+            } => Processor::process_change_volume_limit(
+                program_id, accounts, window, outbound, inbound,
+            ),
+            CTokenInstruction::ChangeTokenFee { bps, min, max } => {
+                Processor::process_change_token_fee(program_id, accounts, bps, min, max)
+            }
+            CTokenInstruction::WithdrawTokenFee { amount } => {
+                Processor::process_withdraw_token_fee(program_id, accounts, amount)
+            }
+            CTokenInstruction::AcceptOwner => Processor::process_accept_owner(program_id, accounts),
+            CTokenInstruction::AcceptAuthority => {
+                Processor::process_accept_authority(program_id, accounts)
+            }
+            CTokenInstruction::CancelPendingOwner => {
+                Processor::process_cancel_pending_owner(program_id, accounts)
+            }
+            CTokenInstruction::CancelPendingAuthority => {
+                Processor::process_cancel_pending_authority(program_id, accounts)
+            }
+            CTokenInstruction::Migrate { remote_decimals } => {
+                Processor::process_migrate(program_id, accounts, remote_decimals)
+            }
+            CTokenInstruction::CloseCToken => {
+                Processor::process_close_c_token(program_id, accounts)
+            }
+            CTokenInstruction::SetLiquidityProvider { enabled } => {
+                Processor::process_set_liquidity_provider(program_id, accounts, enabled)
+            }
+            CTokenInstruction::AddLiquidity { amount } => {
+                Processor::process_add_liquidity(program_id, accounts, amount)
+            }
+            CTokenInstruction::RemoveLiquidity { amount } => {
+                Processor::process_remove_liquidity(program_id, accounts, amount)
+            }
+            CTokenInstruction::ChangeLiquidityReserve { reserve } => {
+                Processor::process_change_liquidity_reserve(program_id, accounts, reserve)
+            }
+            CTokenInstruction::AddRoute {
+                destination,
+                max,
+                min,
+                fee,
+                recipient_format,
+            } => Processor::process_add_route(
+                program_id,
+                accounts,
+                destination,
+                max,
+                min,
+                fee,
+                recipient_format,
+            ),
+            CTokenInstruction::RemoveRoute => Processor::process_remove_route(program_id, accounts),
+            CTokenInstruction::ChangeDecimals { remote_decimals } => {
+                Processor::process_change_decimals(program_id, accounts, remote_decimals)
+            }
+            CTokenInstruction::Block { account } => {
+                Processor::process_block(program_id, accounts, &account)
+            }
+            CTokenInstruction::Unblock => Processor::process_unblock(program_id, accounts),
+            CTokenInstruction::Pause => Processor::process_set_paused(program_id, accounts, true),
+            CTokenInstruction::Unpause => {
+                Processor::process_set_paused(program_id, accounts, false)
             }
         }
     }
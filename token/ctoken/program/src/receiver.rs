@@ -0,0 +1,123 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::state::TransferId;
+
+/// Seed of the caller address signing receiver calls of a cToken
+pub const CALLER_SEED: &[u8] = b"caller";
+
+/// Seed of the receiver program address owning the recipient account of its
+/// calls, binding the receiver to the recipient of the source transfer
+pub const RECIPIENT_OWNER_SEED: &[u8] = b"ctoken";
+
+/// Discriminator of the receiver instruction, the Anchor instruction
+/// discriminator of `ctoken_receive`
+pub const RECEIVE_DISCRIMINATOR: [u8; 8] = [212, 43, 56, 47, 192, 3, 9, 124];
+
+/// Receiver instruction invoked by `SettleAndCall` once the recipient
+/// account received the settled tokens.
+///
+/// Accounts:
+///   0. `[signer]` caller address of the cToken
+///   1. `[]` cToken
+///   2. `[writable]` recipient account
+///   3. `[]` token mint
+///   4. .. additional accounts passed to `SettleAndCall`, never signers
+#[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct Receive {
+    /// Amount the recipient account received in mint decimals, after any
+    /// transfer fee
+    pub amount: u64,
+
+    /// Token mint
+    pub token_mint: Pubkey,
+
+    /// Source transfer
+    pub transfer_id: TransferId,
+
+    /// Sender on the source chain
+    pub sender: Vec<u8>,
+
+    /// Payload of the source transfer
+    pub payload: Vec<u8>,
+}
+
+impl Receive {
+    /// Encodes the instruction data
+    pub fn pack(&self) -> Result<Vec<u8>, ProgramError> {
+        let mut data = RECEIVE_DISCRIMINATOR.to_vec();
+        self.serialize(&mut data)?;
+        Ok(data)
+    }
+
+    /// Decodes the instruction data
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        match input.strip_prefix(&RECEIVE_DISCRIMINATOR) {
+            Some(data) => Ok(Self::try_from_slice(data)?),
+            None => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// Finds the caller address of `c_token`. It only signs receiver calls, so
+/// receivers can't move tokens with its signature.
+pub fn find_caller_address(program_id: &Pubkey, c_token: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CALLER_SEED, &c_token.to_bytes()], program_id)
+}
+
+/// Finds the address of `receiver_program_id` that must own the recipient
+/// token account of its calls, or be the recipient of native cTokens
+pub fn find_recipient_owner_address(receiver_program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RECIPIENT_OWNER_SEED], receiver_program_id)
+}
+
+/// Creates a receiver instruction
+pub fn receive(
+    receiver_program_id: &Pubkey,
+    caller: &Pubkey,
+    c_token: &Pubkey,
+    recipient: &Pubkey,
+    token_mint: &Pubkey,
+    additional_accounts: &[AccountMeta],
+    receive: &Receive,
+) -> Result<Instruction, ProgramError> {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*caller, true),
+        AccountMeta::new_readonly(*c_token, false),
+        AccountMeta::new(*recipient, false),
+        AccountMeta::new_readonly(*token_mint, false),
+    ];
+    accounts.extend_from_slice(additional_accounts);
+
+    Ok(Instruction {
+        program_id: *receiver_program_id,
+        accounts,
+        data: receive.pack()?,
+    })
+}
+
+/// Checks that a receiver call comes from `c_token_program_id`, which
+/// receivers must pin to the cToken program they trust
+pub fn check_caller(
+    c_token_program_id: &Pubkey,
+    caller_info: &AccountInfo,
+    c_token_info: &AccountInfo,
+) -> ProgramResult {
+    if c_token_info.owner != c_token_program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if !caller_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let (caller, _) = find_caller_address(c_token_program_id, c_token_info.key);
+    if *caller_info.key != caller {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    Ok(())
+}
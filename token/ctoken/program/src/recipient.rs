@@ -0,0 +1,195 @@
+use bech32::{FromBase32, Variant};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::keccak;
+
+use crate::error::CTokenError;
+
+/// Size of an EVM address
+pub const EVM_ADDRESS_SIZE: usize = 20;
+
+/// Human readable part of IoTeX native addresses
+pub const IOTEX_HRP: &str = "io";
+
+/// Recipient address format of a bridge destination, stored on the cToken
+/// and each of its routes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub enum RecipientFormat {
+    /// 0x prefixed 20-byte hex, with optional EIP-55 checksum
+    Evm,
+
+    /// io1 bech32 address, or the same as `Evm`
+    IoTeX,
+}
+
+impl RecipientFormat {
+    /// Default recipient format of a well-known `destination`, used by
+    /// clients creating cTokens and routes and by Migrate.
+    /// Mint cTokens created before modes use destination 0 for IoTeX.
+    pub fn for_destination(destination: u32) -> Result<Self, CTokenError> {
+        match destination {
+            0 | 4689 | 4690 => Ok(Self::IoTeX),
+            // Ethereum, BNB Smart Chain, Polygon and their testnets
+            1 | 56 | 97 | 137 | 80002 | 11155111 => Ok(Self::Evm),
+            _ => Err(CTokenError::UnknownDestination),
+        }
+    }
+
+    /// Validates `recipient`, returning its canonical lowercase 0x hex form
+    pub fn normalize(self, recipient: &str) -> Result<String, CTokenError> {
+        let address = match self {
+            Self::IoTeX if recipient.starts_with("io1") => parse_iotex(recipient)?,
+            _ => parse_evm(recipient)?,
+        };
+        Ok(format!("0x{}", hex::encode(address)))
+    }
+}
+
+/// Validates `recipient` for `destination`, returning its canonical form
+pub fn normalize_recipient(destination: u32, recipient: &str) -> Result<String, CTokenError> {
+    RecipientFormat::for_destination(destination)?.normalize(recipient)
+}
+
+/// Address of a recipient returned by `normalize_recipient`
+pub fn recipient_address(recipient: &str) -> Result<[u8; EVM_ADDRESS_SIZE], CTokenError> {
+    parse_evm(recipient)
+}
+
+fn parse_evm(recipient: &str) -> Result<[u8; EVM_ADDRESS_SIZE], CTokenError> {
+    let digits = recipient
+        .strip_prefix("0x")
+        .ok_or(CTokenError::InvalidRecipient)?;
+    let mut address = [0u8; EVM_ADDRESS_SIZE];
+    hex::decode_to_slice(digits, &mut address).map_err(|_| CTokenError::InvalidRecipient)?;
+
+    // mixed case digits carry an EIP-55 checksum
+    let lower = digits.to_ascii_lowercase();
+    if digits != lower && digits != digits.to_ascii_uppercase() && digits != checksum(&lower) {
+        return Err(CTokenError::InvalidRecipient);
+    }
+    Ok(address)
+}
+
+/// EIP-55 checksum casing of lowercase hex `digits`
+fn checksum(digits: &str) -> String {
+    let hash = keccak::hash(digits.as_bytes()).to_bytes();
+    digits
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = if i % 2 == 0 {
+                hash[i / 2] >> 4
+            } else {
+                hash[i / 2] & 0x0f
+            };
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+fn parse_iotex(recipient: &str) -> Result<[u8; EVM_ADDRESS_SIZE], CTokenError> {
+    let (hrp, data, variant) =
+        bech32::decode(recipient).map_err(|_| CTokenError::InvalidRecipient)?;
+    if hrp != IOTEX_HRP || variant != Variant::Bech32 {
+        return Err(CTokenError::InvalidRecipient);
+    }
+    Vec::<u8>::from_base32(&data)
+        .map_err(|_| CTokenError::InvalidRecipient)?
+        .try_into()
+        .map_err(|_| CTokenError::InvalidRecipient)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOWER: &str = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+
+    #[test]
+    fn test_unknown_destination() {
+        assert_eq!(
+            RecipientFormat::for_destination(12345),
+            Err(CTokenError::UnknownDestination)
+        );
+        assert_eq!(
+            normalize_recipient(12345, LOWER),
+            Err(CTokenError::UnknownDestination)
+        );
+        assert_eq!(
+            RecipientFormat::for_destination(0),
+            Ok(RecipientFormat::IoTeX)
+        );
+        assert_eq!(
+            RecipientFormat::for_destination(1),
+            Ok(RecipientFormat::Evm)
+        );
+    }
+
+    #[test]
+    fn test_eip55_checksum() {
+        // vectors from EIP-55
+        for recipient in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            assert_eq!(
+                normalize_recipient(1, recipient),
+                Ok(recipient.to_ascii_lowercase())
+            );
+        }
+        assert_eq!(
+            normalize_recipient(1, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"),
+            Err(CTokenError::InvalidRecipient)
+        );
+        assert_eq!(
+            normalize_recipient(1, "0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED"),
+            Ok(LOWER.to_string())
+        );
+    }
+
+    #[test]
+    fn test_lowercase_hex() {
+        assert_eq!(normalize_recipient(1, LOWER), Ok(LOWER.to_string()));
+        assert_eq!(normalize_recipient(4689, LOWER), Ok(LOWER.to_string()));
+        assert_eq!(
+            normalize_recipient(1, &LOWER[2..]),
+            Err(CTokenError::InvalidRecipient)
+        );
+        assert_eq!(
+            normalize_recipient(1, &LOWER[..40]),
+            Err(CTokenError::InvalidRecipient)
+        );
+    }
+
+    #[test]
+    fn test_iotex_bech32() {
+        let recipient = "io1t2htvpfl862vnwdqnuekd9p4ulh3h6hdf9mua7";
+        assert_eq!(normalize_recipient(4689, recipient), Ok(LOWER.to_string()));
+        assert_eq!(normalize_recipient(0, recipient), Ok(LOWER.to_string()));
+        // bech32 only on IoTeX destinations
+        assert_eq!(
+            normalize_recipient(1, recipient),
+            Err(CTokenError::InvalidRecipient)
+        );
+        // bad checksum
+        assert_eq!(
+            normalize_recipient(4689, "io1t2htvpfl862vnwdqnuekd9p4ulh3h6hdf9mua8"),
+            Err(CTokenError::InvalidRecipient)
+        );
+        // other human readable part
+        assert_eq!(
+            normalize_recipient(4689, "it1t2htvpfl862vnwdqnuekd9p4ulh3h6hd2yxlka"),
+            Err(CTokenError::InvalidRecipient)
+        );
+        // 19 bytes
+        assert_eq!(
+            normalize_recipient(4689, "io1t2htvpfl862vnwdqnuekd9p4ulh3h6s07p7th"),
+            Err(CTokenError::InvalidRecipient)
+        );
+    }
+}
@@ -1,11 +1,65 @@
-use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::pubkey::Pubkey;
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    error::CTokenError,
+    recipient::{RecipientFormat, EVM_ADDRESS_SIZE},
+};
+
+/// Type of program account, stored in the first byte of account data
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub enum AccountType {
+    /// Not initialized
+    Uninitialized,
+
+    /// Config or cToken created before versioning, whose first byte is
+    /// `is_initialized`. Must be migrated before use.
+    Legacy,
+
+    /// Config
+    Config,
+
+    /// CToken
+    CToken,
+
+    /// Settlement receipt
+    SettlementReceipt,
+
+    /// Liquidity provider of a cToken vault
+    LiquidityProvider,
+
+    /// Additional destination route of a cToken
+    Route,
+
+    /// Blocklist entry of a config
+    BlockedAccount,
+
+    /// Receipt of an outbound transfer
+    BridgeReceipt,
+}
+
+/// Deserializes an account of `account_type`, rejecting other account types
+/// and legacy accounts. Account data may be longer than the serialized
+/// account.
+fn unpack_account<T: BorshDeserialize>(
+    data: &[u8],
+    account_type: AccountType,
+) -> Result<T, ProgramError> {
+    match data.first() {
+        Some(&t) if t == account_type as u8 => Ok(T::deserialize(&mut &data[..])?),
+        Some(&t) if t == AccountType::Legacy as u8 => Err(CTokenError::AccountNotMigrated.into()),
+        _ => Err(CTokenError::InvalidAccountType.into()),
+    }
+}
 
 /// Crosschain global config
 #[derive(Clone, Debug, BorshDeserialize, BorshSerialize)]
 pub struct Config {
-    /// Initialized state.
-    pub is_initialized: bool,
+    /// Account type
+    pub account_type: AccountType,
+
+    /// Layout version
+    pub version: u8,
 
     /// Owner
     pub owner: Pubkey,
@@ -18,13 +72,94 @@ pub struct Config {
 
     /// Fee collector
     pub fee_collector: Pubkey,
+
+    /// Paused state, halts every cToken under this config
+    pub paused: bool,
+
+    /// Proposed owner, waiting for acceptance
+    pub pending_owner: Option<Pubkey>,
+
+    /// Proposed authority, waiting for acceptance
+    pub pending_authority: Option<Pubkey>,
 }
 
+impl Config {
+    pub const LEN: usize = 1 + 1 + 32 + 32 + 8 + 32 + 1 + 33 + 33;
+    pub const SEED: &'static [u8] = b"config";
+    pub const RENT_VAULT_SEED: &'static [u8] = b"rent";
+    pub const DROP_OFF_VAULT_SEED: &'static [u8] = b"drop-off";
+    pub const VERSION: u8 = 1;
+
+    /// Program address of the config of `owner` with `seed`, so no one else
+    /// can take the address first
+    pub fn find_address(program_id: &Pubkey, owner: &Pubkey, seed: &str) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[Self::SEED, &owner.to_bytes(), seed.as_bytes()],
+            program_id,
+        )
+    }
+
+    /// Program address of the lamport vault of `config` paying the rent of
+    /// recipient token accounts created on settle
+    pub fn find_rent_vault_address(program_id: &Pubkey, config: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::RENT_VAULT_SEED, &config.to_bytes()], program_id)
+    }
+
+    /// Program address of the lamport vault of `config` paying drop-offs to
+    /// recipient wallets on settle
+    pub fn find_drop_off_vault_address(program_id: &Pubkey, config: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::DROP_OFF_VAULT_SEED, &config.to_bytes()], program_id)
+    }
+
+    /// Deserializes config from account data, which may be longer than the
+    /// serialized config while pending changes are unset.
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        let config: Self = unpack_account(data, AccountType::Config)?;
+        if config.version != Self::VERSION {
+            return Err(CTokenError::AccountNotMigrated.into());
+        }
+        Ok(config)
+    }
+}
+
+/// How a cToken holds bridged tokens
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub enum CTokenMode {
+    /// Locks tokens in the vault on bridge and unlocks them on settle
+    Lock,
+
+    /// Burns tokens on bridge and mints them on settle
+    Mint,
+
+    /// Locks SOL in the cToken authority on bridge and unlocks it to system
+    /// accounts on settle
+    Native,
+}
+
+impl CTokenMode {
+    /// Mode of cTokens created before modes, which used destination 0 for
+    /// mint mode
+    pub fn from_legacy_destination(destination: u32) -> Self {
+        if destination == 0 {
+            Self::Mint
+        } else {
+            Self::Lock
+        }
+    }
+}
+
+/// Token mint of native cTokens, the SPL Token native mint
+pub const NATIVE_MINT: Pubkey =
+    solana_program::pubkey!("So11111111111111111111111111111111111111112");
+
 /// Crosschain Token
 #[derive(Clone, Debug, BorshDeserialize, BorshSerialize)]
 pub struct CToken {
-    /// Initialized state.
-    pub is_initialized: bool,
+    /// Account type
+    pub account_type: AccountType,
+
+    /// Layout version
+    pub version: u8,
 
     /// Bump seed used in program address.
     /// The program address is created deterministically with the bump seed,
@@ -38,14 +173,13 @@ pub struct CToken {
     /// CToken config
     pub config: Pubkey,
 
-    /// Token account for cToken bridge
+    /// Vault token account of a lock cToken
     pub token: Pubkey,
     /// Mint information for token
     pub token_mint: Pubkey,
 
-    /// Bridge chain id
-    /// 0: solana
-    /// 4689: IoTeX
+    /// Bridge chain id, e.g. 4689 for IoTeX.
+    /// cTokens created before modes use 0 for mint mode.
     pub destination: u32,
 
     /// Index for bridge instruction
@@ -56,4 +190,758 @@ pub struct CToken {
 
     /// Min amount for bridge
     pub min: u64,
+
+    /// Paused state
+    pub paused: bool,
+
+    /// Time window in seconds for volume limits
+    pub window: i64,
+
+    /// Volume limit for bridge
+    pub outbound: VolumeLimit,
+
+    /// Volume limit for settle
+    pub inbound: VolumeLimit,
+
+    /// Bridge fee in token, basis points of amount
+    pub fee_bps: u16,
+
+    /// Min fee in token
+    pub min_fee: u64,
+
+    /// Max fee in token, 0 for no max
+    pub max_fee: u64,
+
+    /// Token account collecting token fee, owned by cToken authority
+    pub fee_token: Pubkey,
+
+    /// Liquidity provided to the vault and not yet removed
+    pub liquidity: u64,
+
+    /// Vault balance that liquidity removals must leave for settlements
+    pub liquidity_reserve: u64,
+
+    /// Custody mode
+    pub mode: CTokenMode,
+
+    /// Decimals of the token mint
+    pub decimals: u8,
+
+    /// Decimals of the token on the destination chain. Amounts are not
+    /// scaled while equal to `decimals`
+    pub remote_decimals: u8,
+
+    /// Age in seconds after which bridge receipts can be closed, 0 when
+    /// bridge writes no receipts
+    pub receipt_age: i64,
+
+    /// Lamports paid from the drop-off vault of the config to recipient
+    /// wallets on settle, 0 for no drop-off
+    pub drop_off: u64,
+
+    /// Wallet balance in lamports below which settle pays the drop-off
+    pub drop_off_threshold: u64,
+
+    /// Bridge receipts not closed yet, which must be closed before the
+    /// cToken so a cToken recreated at its address starts with free indices
+    pub open_receipts: u64,
+
+    /// Recipient address format of `destination`
+    pub recipient_format: RecipientFormat,
+}
+
+impl CToken {
+    pub const LEN: usize = 1
+        + 1
+        + 1
+        + 32
+        + 32
+        + 32
+        + 32
+        + 4
+        + 8
+        + 8
+        + 8
+        + 1
+        + 8
+        + VolumeLimit::LEN * 2
+        + 2
+        + 8
+        + 8
+        + 32
+        + 8
+        + 8
+        + 1
+        + 1
+        + 1
+        + 8
+        + 8
+        + 8
+        + 8
+        + 1;
+    pub const SEED: &'static [u8] = b"ctoken";
+    pub const VERSION: u8 = 8;
+
+    /// First version with an explicit mode
+    pub const MODE_VERSION: u8 = 3;
+
+    /// First version with mint and remote decimals
+    pub const DECIMALS_VERSION: u8 = 4;
+
+    /// First version with a recipient format
+    pub const RECIPIENT_FORMAT_VERSION: u8 = 8;
+
+    /// Max basis points for token fee
+    pub const MAX_FEE_BPS: u16 = 10_000;
+
+    /// Max difference of mint and remote decimals, keeping scaled amounts
+    /// within u128
+    pub const MAX_DECIMALS_DIFF: u8 = 38;
+
+    /// Program address of the cToken for `token_mint` bridging to `destination`
+    pub fn find_address(
+        program_id: &Pubkey,
+        config: &Pubkey,
+        token_mint: &Pubkey,
+        destination: u32,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                Self::SEED,
+                &config.to_bytes(),
+                &token_mint.to_bytes(),
+                &destination.to_le_bytes(),
+            ],
+            program_id,
+        )
+    }
+
+    /// Program address of the authority of `c_token`, which owns its vault
+    /// and mints its token
+    pub fn find_authority_address(program_id: &Pubkey, c_token: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[&c_token.to_bytes()], program_id)
+    }
+
+    /// Deserializes cToken from account data
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        let c_token: Self = unpack_account(data, AccountType::CToken)?;
+        if c_token.version != Self::VERSION {
+            return Err(CTokenError::AccountNotMigrated.into());
+        }
+        Ok(c_token)
+    }
+
+    /// Deserializes cToken of the current or an earlier version. Fields
+    /// appended since the earlier version read as zero once the account is
+    /// reallocated to `LEN`.
+    pub fn unpack_outdated(data: &[u8]) -> Result<Self, ProgramError> {
+        unpack_account(data, AccountType::CToken)
+    }
+
+    /// Whether bridge charges a fee in token
+    pub fn has_token_fee(&self) -> bool {
+        self.fee_bps > 0 || self.min_fee > 0
+    }
+
+    /// Token fee for bridging `amount`
+    pub fn token_fee(&self, amount: u64) -> Result<u64, CTokenError> {
+        if !self.has_token_fee() {
+            return Ok(0);
+        }
+        let mut fee = (amount as u128 * self.fee_bps as u128 / Self::MAX_FEE_BPS as u128) as u64;
+        fee = fee.max(self.min_fee);
+        if self.max_fee > 0 {
+            fee = fee.min(self.max_fee);
+        }
+        if fee >= amount {
+            return Err(CTokenError::InvalidAmount);
+        }
+        Ok(fee)
+    }
+
+    /// Scales a mint `amount` to remote decimals, refusing amounts with
+    /// digits the remote token can't represent
+    pub fn to_remote_amount(&self, amount: u64) -> Result<u128, CTokenError> {
+        let amount = amount as u128;
+        if self.remote_decimals >= self.decimals {
+            let factor = decimal_factor(self.remote_decimals - self.decimals)?;
+            amount.checked_mul(factor).ok_or(CTokenError::InvalidAmount)
+        } else {
+            let factor = decimal_factor(self.decimals - self.remote_decimals)?;
+            if amount % factor != 0 {
+                return Err(CTokenError::InvalidAmount);
+            }
+            Ok(amount / factor)
+        }
+    }
+
+    /// Scales a remote `amount` to mint decimals, returning the scaled amount
+    /// and the truncated remote dust
+    pub fn to_local_amount(&self, amount: u128) -> Result<(u64, u128), CTokenError> {
+        let (local, dust) = if self.remote_decimals >= self.decimals {
+            let factor = decimal_factor(self.remote_decimals - self.decimals)?;
+            (amount / factor, amount % factor)
+        } else {
+            let factor = decimal_factor(self.decimals - self.remote_decimals)?;
+            (
+                amount
+                    .checked_mul(factor)
+                    .ok_or(CTokenError::InvalidAmount)?,
+                0,
+            )
+        };
+        let local = u64::try_from(local).map_err(|_| CTokenError::InvalidAmount)?;
+        Ok((local, dust))
+    }
+}
+
+/// 10 to the power of a decimals difference
+fn decimal_factor(decimals: u8) -> Result<u128, CTokenError> {
+    10u128
+        .checked_pow(decimals as u32)
+        .ok_or(CTokenError::InvalidInput)
+}
+
+/// Rolling window volume limit
+#[derive(Clone, Debug, Default, BorshDeserialize, BorshSerialize)]
+pub struct VolumeLimit {
+    /// Max volume in a window, 0 for unlimited
+    pub cap: u64,
+
+    /// Volume of current window
+    pub volume: u64,
+
+    /// Volume of previous window
+    pub previous_volume: u64,
+
+    /// Start timestamp of current window
+    pub window_start: i64,
+}
+
+impl VolumeLimit {
+    pub const LEN: usize = 8 + 8 + 8 + 8;
+
+    pub fn new(cap: u64) -> Self {
+        Self {
+            cap,
+            ..Default::default()
+        }
+    }
+
+    /// Records `amount` at `now`, failing if the volume of the last `window`
+    /// seconds would exceed the cap. The previous window is weighted by the
+    /// part of it still inside the rolling window.
+    pub fn consume(&mut self, amount: u64, now: i64, window: i64) -> Result<(), CTokenError> {
+        if self.cap == 0 || window <= 0 {
+            return Ok(());
+        }
+        let windows = now.saturating_sub(self.window_start) / window;
+        if windows > 0 {
+            self.previous_volume = if windows == 1 { self.volume } else { 0 };
+            self.volume = 0;
+            self.window_start = now - now.saturating_sub(self.window_start) % window;
+        }
+
+        let remaining = (window - (now - self.window_start)) as u128;
+        let weighted = (self.previous_volume as u128 * remaining / window as u128) as u64;
+        let volume = self
+            .volume
+            .checked_add(amount)
+            .ok_or(CTokenError::InvalidAmount)?;
+        if weighted.saturating_add(volume) > self.cap {
+            return Err(CTokenError::VolumeLimitExceeded);
+        }
+        self.volume = volume;
+
+        Ok(())
+    }
+}
+
+/// Size of cashier address on source chain
+pub const CASHIER_ADDRESS_SIZE: usize = 20;
+
+/// Identifier of a transfer on the source chain
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct TransferId {
+    /// Source chain id
+    pub source_chain: u32,
+
+    /// Cashier contract on source chain
+    pub cashier: [u8; CASHIER_ADDRESS_SIZE],
+
+    /// Transfer index in cashier
+    pub index: u64,
+}
+
+/// Receipt of a settled transfer, one per transfer id under a config
+#[derive(Clone, Debug, BorshDeserialize, BorshSerialize)]
+pub struct SettlementReceipt {
+    /// Account type
+    pub account_type: AccountType,
+
+    /// Settled cToken
+    pub c_token: Pubkey,
+
+    /// Source transfer
+    pub transfer_id: TransferId,
+
+    /// Recipient token account
+    pub recipient: Pubkey,
+
+    /// Settled amount
+    pub amount: u64,
+
+    /// Settle timestamp
+    pub settled_at: i64,
+}
+
+impl SettlementReceipt {
+    pub const LEN: usize = 1 + 32 + (4 + CASHIER_ADDRESS_SIZE + 8) + 32 + 8 + 8;
+    pub const SEED: &'static [u8] = b"settlement";
+
+    /// Program address of the receipt for `transfer_id` under `config`
+    pub fn find_address(
+        program_id: &Pubkey,
+        config: &Pubkey,
+        transfer_id: &TransferId,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                Self::SEED,
+                &config.to_bytes(),
+                &transfer_id.source_chain.to_le_bytes(),
+                &transfer_id.cashier,
+                &transfer_id.index.to_le_bytes(),
+            ],
+            program_id,
+        )
+    }
+
+    /// Deserializes settlement receipt from account data
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        unpack_account(data, AccountType::SettlementReceipt)
+    }
+}
+
+/// Liquidity provider of a lock cToken vault, one per provider and cToken
+#[derive(Clone, Debug, BorshDeserialize, BorshSerialize)]
+pub struct LiquidityProvider {
+    /// Account type
+    pub account_type: AccountType,
+
+    /// CToken whose vault the liquidity is provided to
+    pub c_token: Pubkey,
+
+    /// Provider wallet
+    pub provider: Pubkey,
+
+    /// Whitelisted to add liquidity
+    pub enabled: bool,
+
+    /// Liquidity provided and not yet removed
+    pub amount: u64,
+}
+
+impl LiquidityProvider {
+    pub const LEN: usize = 1 + 32 + 32 + 1 + 8;
+    pub const SEED: &'static [u8] = b"liquidity";
+
+    /// Program address of `provider` for `c_token`
+    pub fn find_address(program_id: &Pubkey, c_token: &Pubkey, provider: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[Self::SEED, &c_token.to_bytes(), &provider.to_bytes()],
+            program_id,
+        )
+    }
+
+    /// Deserializes liquidity provider from account data
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        unpack_account(data, AccountType::LiquidityProvider)
+    }
+}
+
+/// Destination route of a cToken besides its own destination, one per
+/// destination and cToken
+#[derive(Clone, Debug, BorshDeserialize, BorshSerialize)]
+pub struct Route {
+    /// Account type
+    pub account_type: AccountType,
+
+    /// CToken
+    pub c_token: Pubkey,
+
+    /// Bridge chain id
+    pub destination: u32,
+
+    /// Max amount for bridge
+    pub max: u64,
+
+    /// Min amount for bridge
+    pub min: u64,
+
+    /// Bridge fee in lamports, replacing the config fee
+    pub fee: u64,
+
+    /// Paused state
+    pub paused: bool,
+
+    /// Recipient address format of `destination`
+    pub recipient_format: RecipientFormat,
+}
+
+impl Route {
+    pub const LEN: usize = 1 + 32 + 4 + 8 + 8 + 8 + 1 + 1;
+    pub const SEED: &'static [u8] = b"route";
+
+    /// Program address of the route of `c_token` to `destination`
+    pub fn find_address(program_id: &Pubkey, c_token: &Pubkey, destination: u32) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[Self::SEED, &c_token.to_bytes(), &destination.to_le_bytes()],
+            program_id,
+        )
+    }
+
+    /// Deserializes route from account data
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        unpack_account(data, AccountType::Route)
+    }
+}
+
+/// Blocklist entry, an account that can't bridge or receive settlements of
+/// a config. Exists at a program address derived from config and account.
+#[derive(Clone, Debug, BorshDeserialize, BorshSerialize)]
+pub struct BlockedAccount {
+    /// Account type
+    pub account_type: AccountType,
+
+    /// Config
+    pub config: Pubkey,
+
+    /// Blocked wallet
+    pub account: Pubkey,
+}
+
+impl BlockedAccount {
+    pub const LEN: usize = 1 + 32 + 32;
+    pub const SEED: &'static [u8] = b"blocked";
+
+    /// Program address of the blocklist entry of `account`
+    pub fn find_address(program_id: &Pubkey, config: &Pubkey, account: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[Self::SEED, &config.to_bytes(), &account.to_bytes()],
+            program_id,
+        )
+    }
+
+    /// Deserializes blocklist entry from account data
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        unpack_account(data, AccountType::BlockedAccount)
+    }
+}
+
+/// Receipt of an outbound transfer, one per cToken and bridge index
+#[derive(Clone, Debug, BorshDeserialize, BorshSerialize)]
+pub struct BridgeReceipt {
+    /// Account type
+    pub account_type: AccountType,
+
+    /// Bridged cToken
+    pub c_token: Pubkey,
+
+    /// Bridge index
+    pub index: u64,
+
+    /// Sender
+    pub sender: Pubkey,
+
+    /// Recipient
+    pub recipient: [u8; EVM_ADDRESS_SIZE],
+
+    /// Amount after token fee
+    pub amount: u64,
+
+    /// Fee in lamports
+    pub fee: u64,
+
+    /// Token fee
+    pub token_fee: u64,
+
+    /// Destination
+    pub destination: u32,
+
+    /// Keccak hash of the payload
+    pub payload_hash: [u8; 32],
+
+    /// Bridge slot
+    pub slot: u64,
+
+    /// Bridge timestamp
+    pub bridged_at: i64,
+
+    /// Account paying the rent, refunded on close
+    pub payer: Pubkey,
+}
+
+impl BridgeReceipt {
+    pub const LEN: usize = 1 + 32 + 8 + 32 + EVM_ADDRESS_SIZE + 8 + 8 + 8 + 4 + 32 + 8 + 8 + 32;
+    pub const SEED: &'static [u8] = b"bridge";
+
+    /// Program address of the receipt of bridge `index` of `c_token`
+    pub fn find_address(program_id: &Pubkey, c_token: &Pubkey, index: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[Self::SEED, &c_token.to_bytes(), &index.to_le_bytes()],
+            program_id,
+        )
+    }
+
+    /// Deserializes bridge receipt from account data
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        unpack_account(data, AccountType::BridgeReceipt)
+    }
+}
+
+/// Config layout before versioning
+#[derive(Clone, Debug, BorshDeserialize, BorshSerialize)]
+pub struct LegacyConfig {
+    pub is_initialized: bool,
+    pub owner: Pubkey,
+    pub authority: Pubkey,
+    pub fee: u64,
+    pub fee_collector: Pubkey,
+}
+
+impl From<LegacyConfig> for Config {
+    fn from(legacy: LegacyConfig) -> Self {
+        Self {
+            account_type: AccountType::Config,
+            version: Self::VERSION,
+            owner: legacy.owner,
+            authority: legacy.authority,
+            fee: legacy.fee,
+            fee_collector: legacy.fee_collector,
+            paused: false,
+            pending_owner: None,
+            pending_authority: None,
+        }
+    }
+}
+
+/// CToken layout before versioning
+#[derive(Clone, Debug, BorshDeserialize, BorshSerialize)]
+pub struct LegacyCToken {
+    pub is_initialized: bool,
+    pub bump_seed: u8,
+    pub token_program_id: Pubkey,
+    pub config: Pubkey,
+    pub token: Pubkey,
+    pub token_mint: Pubkey,
+    pub destination: u32,
+    pub index: u64,
+    pub max: u64,
+    pub min: u64,
+}
+
+impl From<LegacyCToken> for CToken {
+    fn from(legacy: LegacyCToken) -> Self {
+        Self {
+            account_type: AccountType::CToken,
+            version: Self::VERSION,
+            bump_seed: legacy.bump_seed,
+            token_program_id: legacy.token_program_id,
+            config: legacy.config,
+            token: legacy.token,
+            token_mint: legacy.token_mint,
+            destination: legacy.destination,
+            index: legacy.index,
+            max: legacy.max,
+            min: legacy.min,
+            paused: false,
+            window: 0,
+            outbound: VolumeLimit::default(),
+            inbound: VolumeLimit::default(),
+            fee_bps: 0,
+            min_fee: 0,
+            max_fee: 0,
+            fee_token: Pubkey::default(),
+            liquidity: 0,
+            liquidity_reserve: 0,
+            mode: CTokenMode::from_legacy_destination(legacy.destination),
+            // set by Migrate from the mint and its argument
+            decimals: 0,
+            remote_decimals: 0,
+            receipt_age: 0,
+            drop_off: 0,
+            drop_off_threshold: 0,
+            open_receipts: 0,
+            recipient_format: RecipientFormat::for_destination(legacy.destination)
+                .unwrap_or(RecipientFormat::Evm),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// cToken with every field zeroed
+    fn c_token() -> CToken {
+        CToken::deserialize(&mut &[0u8; CToken::LEN][..]).unwrap()
+    }
+
+    fn with_fee(fee_bps: u16, min_fee: u64, max_fee: u64) -> CToken {
+        CToken {
+            fee_bps,
+            min_fee,
+            max_fee,
+            ..c_token()
+        }
+    }
+
+    fn with_decimals(decimals: u8, remote_decimals: u8) -> CToken {
+        CToken {
+            decimals,
+            remote_decimals,
+            ..c_token()
+        }
+    }
+
+    #[test]
+    fn test_volume_limit_unlimited() {
+        let mut limit = VolumeLimit::new(0);
+        assert_eq!(limit.consume(u64::MAX, 0, 100), Ok(()));
+        let mut limit = VolumeLimit::new(100);
+        assert_eq!(limit.consume(1000, 0, 0), Ok(()));
+        assert_eq!(limit.volume, 0);
+    }
+
+    #[test]
+    fn test_volume_limit_window() {
+        let mut limit = VolumeLimit::new(100);
+        assert_eq!(limit.consume(60, 0, 100), Ok(()));
+        assert_eq!(limit.consume(40, 50, 100), Ok(()));
+        assert_eq!(
+            limit.consume(1, 99, 100),
+            Err(CTokenError::VolumeLimitExceeded)
+        );
+        assert_eq!(limit.volume, 100);
+    }
+
+    #[test]
+    fn test_volume_limit_rollover() {
+        let mut limit = VolumeLimit::new(100);
+        assert_eq!(limit.consume(100, 0, 100), Ok(()));
+
+        // the previous window still counts in full at the start of the next
+        assert_eq!(
+            limit.consume(1, 100, 100),
+            Err(CTokenError::VolumeLimitExceeded)
+        );
+        assert_eq!(limit.window_start, 100);
+        assert_eq!(limit.previous_volume, 100);
+        assert_eq!(limit.volume, 0);
+
+        // and half of it halfway through
+        assert_eq!(limit.consume(50, 150, 100), Ok(()));
+        assert_eq!(
+            limit.consume(1, 150, 100),
+            Err(CTokenError::VolumeLimitExceeded)
+        );
+
+        // a skipped window clears the previous volume
+        assert_eq!(limit.consume(100, 350, 100), Ok(()));
+        assert_eq!(limit.window_start, 300);
+        assert_eq!(limit.previous_volume, 0);
+        assert_eq!(limit.volume, 100);
+    }
+
+    #[test]
+    fn test_volume_limit_overflow() {
+        let mut limit = VolumeLimit::new(u64::MAX);
+        limit.volume = u64::MAX;
+        assert_eq!(limit.consume(1, 0, 100), Err(CTokenError::InvalidAmount));
+        assert_eq!(limit.volume, u64::MAX);
+    }
+
+    #[test]
+    fn test_token_fee() {
+        assert_eq!(with_fee(0, 0, 0).token_fee(1000), Ok(0));
+        assert_eq!(with_fee(30, 0, 0).token_fee(1000), Ok(3));
+        // rounds down
+        assert_eq!(with_fee(30, 0, 0).token_fee(999), Ok(2));
+        assert_eq!(with_fee(30, 0, 0).token_fee(33), Ok(0));
+        // min and max bound the basis point fee
+        assert_eq!(with_fee(30, 5, 0).token_fee(1000), Ok(5));
+        assert_eq!(with_fee(30, 0, 10).token_fee(1_000_000), Ok(10));
+        assert_eq!(with_fee(0, 5, 0).token_fee(1000), Ok(5));
+        // fee must leave something to bridge
+        assert_eq!(
+            with_fee(0, 5, 0).token_fee(5),
+            Err(CTokenError::InvalidAmount)
+        );
+        assert_eq!(
+            with_fee(CToken::MAX_FEE_BPS, 0, 0).token_fee(u64::MAX),
+            Err(CTokenError::InvalidAmount)
+        );
+        assert_eq!(with_fee(5000, 0, 0).token_fee(u64::MAX), Ok(u64::MAX / 2));
+    }
+
+    #[test]
+    fn test_same_decimals() {
+        let c_token = with_decimals(9, 9);
+        assert_eq!(c_token.to_remote_amount(u64::MAX), Ok(u64::MAX as u128));
+        assert_eq!(c_token.to_local_amount(u64::MAX as u128), Ok((u64::MAX, 0)));
+        assert_eq!(
+            c_token.to_local_amount(u64::MAX as u128 + 1),
+            Err(CTokenError::InvalidAmount)
+        );
+    }
+
+    #[test]
+    fn test_more_remote_decimals() {
+        let c_token = with_decimals(6, 18);
+        assert_eq!(c_token.to_remote_amount(1), Ok(1_000_000_000_000));
+        // remote digits the mint can't hold are dust
+        assert_eq!(c_token.to_local_amount(1_000_000_000_005), Ok((1, 5)));
+        assert_eq!(
+            c_token.to_local_amount(999_999_999_999),
+            Ok((0, 999_999_999_999))
+        );
+        assert_eq!(
+            c_token.to_local_amount(u128::MAX),
+            Err(CTokenError::InvalidAmount)
+        );
+    }
+
+    #[test]
+    fn test_fewer_remote_decimals() {
+        let c_token = with_decimals(18, 6);
+        assert_eq!(c_token.to_remote_amount(1_000_000_000_000), Ok(1));
+        // bridge refuses digits the remote token can't hold
+        assert_eq!(
+            c_token.to_remote_amount(1_000_000_000_001),
+            Err(CTokenError::InvalidAmount)
+        );
+        assert_eq!(c_token.to_local_amount(1), Ok((1_000_000_000_000, 0)));
+        assert_eq!(
+            c_token.to_local_amount(u64::MAX as u128),
+            Err(CTokenError::InvalidAmount)
+        );
+    }
+
+    #[test]
+    fn test_decimals_overflow() {
+        assert_eq!(
+            with_decimals(0, 38).to_remote_amount(u64::MAX),
+            Err(CTokenError::InvalidAmount)
+        );
+        assert_eq!(
+            with_decimals(38, 0).to_local_amount(u128::MAX),
+            Err(CTokenError::InvalidAmount)
+        );
+        // 10^39 doesn't fit u128
+        assert_eq!(
+            with_decimals(0, 39).to_remote_amount(1),
+            Err(CTokenError::InvalidInput)
+        );
+    }
 }
@@ -12,6 +12,12 @@ pub enum HelloError {
     /// Invalid program address
     #[error("Invalid program address generated from bump seed and key")]
     InvalidProgramAddress,
+    /// Invalid payload
+    #[error("Payload is not a wallet address")]
+    InvalidPayload,
+    /// Invalid destination
+    #[error("Destination is not a token account of the payload wallet")]
+    InvalidDestination,
 }
 impl From<HelloError> for ProgramError {
     fn from(e: HelloError) -> Self {
@@ -37,6 +43,10 @@ impl PrintProgramError for HelloError {
             HelloError::InvalidProgramAddress => {
                 msg!("Error: Invalid program address generated from bump seed and key")
             }
+            HelloError::InvalidPayload => msg!("Error: Payload is not a wallet address"),
+            HelloError::InvalidDestination => {
+                msg!("Error: Destination is not a token account of the payload wallet")
+            }
         }
     }
 }
@@ -1,17 +1,50 @@
-use ctoken::instruction;
+use std::str::FromStr;
+
+use borsh::BorshDeserialize;
+use ctoken::{
+    instruction,
+    processor::Processor as CTokenProcessor,
+    receiver::{self, Receive, RECEIVE_DISCRIMINATOR},
+    state::TransferId,
+};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
+    msg,
     program::invoke_signed,
     program_error::ProgramError,
     pubkey::Pubkey,
 };
 
+use crate::error::HelloError;
+
+/// cToken program trusted to call the receiver, the one of
+/// `CTOKEN_PROGRAM_ID` at build time or the mainnet cToken program
+pub fn c_token_program_id() -> Result<Pubkey, ProgramError> {
+    match option_env!("CTOKEN_PROGRAM_ID") {
+        Some(id) => Pubkey::from_str(id).map_err(|_| ProgramError::InvalidArgument),
+        None => Ok(ctoken::id()),
+    }
+}
+
 pub struct Processor {}
 
 impl Processor {
     /// Processes an [Instruction](enum.Instruction.html).
-    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], _input: &[u8]) -> ProgramResult {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
+        if input.starts_with(&RECEIVE_DISCRIMINATOR) {
+            Self::process_receive(program_id, accounts, Receive::unpack(input)?)
+        } else {
+            Self::process_settle(program_id, accounts, TransferId::try_from_slice(input)?)
+        }
+    }
+
+    /// Settles a transfer through the cToken program as config authority
+    pub fn process_settle(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        transfer_id: TransferId,
+    ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
 
         let c_token_program_info = next_account_info(account_info_iter)?;
@@ -23,23 +56,32 @@ impl Processor {
         let token_mint_info = next_account_info(account_info_iter)?;
         let config_info = next_account_info(account_info_iter)?;
         let token_program_info = next_account_info(account_info_iter)?;
+        let receipt_info = next_account_info(account_info_iter)?;
+        let payer_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let blocked_info = next_account_info(account_info_iter)?;
 
         let (owner, bump_seed) = Pubkey::find_program_address(&[b"ctoken"], program_id);
         if *owner_info.key != owner {
             return Err(ProgramError::InvalidAccountOwner);
         }
 
+        let user_owner =
+            CTokenProcessor::unpack_token_account(user_info, token_program_info.key)?.owner;
         let tx = instruction::settle(
-            &c_token_program_info.key,
-            &c_token_info.key,
-            &authority_info.key,
-            &c_token_token_info.key,
-            &user_info.key,
-            &owner_info.key,
-            &token_mint_info.key,
-            &config_info.key,
-            &token_program_info.key,
+            c_token_program_info.key,
+            c_token_info.key,
+            authority_info.key,
+            c_token_token_info.key,
+            user_info.key,
+            &user_owner,
+            owner_info.key,
+            token_mint_info.key,
+            config_info.key,
+            token_program_info.key,
+            payer_info.key,
             20000000,
+            transfer_id,
         )?;
 
         invoke_signed(
@@ -53,10 +95,82 @@ impl Processor {
                 token_mint_info.clone(),
                 config_info.clone(),
                 token_program_info.clone(),
+                receipt_info.clone(),
+                payer_info.clone(),
+                system_program_info.clone(),
+                blocked_info.clone(),
             ],
             &[&[b"ctoken", &[bump_seed]]],
         )?;
 
         Ok(())
     }
+
+    /// Receives tokens settled with `SettleAndCall` into its token account
+    /// and forwards them to the token account of the wallet in the payload
+    pub fn process_receive(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        receive: Receive,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let caller_info = next_account_info(account_info_iter)?;
+        let c_token_info = next_account_info(account_info_iter)?;
+        let recipient_info = next_account_info(account_info_iter)?;
+        let token_mint_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let destination_info = next_account_info(account_info_iter)?;
+
+        receiver::check_caller(&c_token_program_id()?, caller_info, c_token_info)?;
+
+        let (owner, bump_seed) = receiver::find_recipient_owner_address(program_id);
+        if *owner_info.key != owner {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        let recipient =
+            CTokenProcessor::unpack_token_account(recipient_info, token_program_info.key)?;
+        if recipient.owner != owner {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        let wallet =
+            Pubkey::try_from(receive.payload.as_slice()).map_err(|_| HelloError::InvalidPayload)?;
+        let destination =
+            CTokenProcessor::unpack_token_account(destination_info, token_program_info.key)?;
+        if destination.owner != wallet || destination.mint != receive.token_mint {
+            return Err(HelloError::InvalidDestination.into());
+        }
+        let token_mint = CTokenProcessor::unpack_mint(token_mint_info, token_program_info.key)?;
+
+        invoke_signed(
+            &spl_token_2022::instruction::transfer_checked(
+                token_program_info.key,
+                recipient_info.key,
+                token_mint_info.key,
+                destination_info.key,
+                owner_info.key,
+                &[],
+                receive.amount,
+                token_mint.decimals,
+            )?,
+            &[
+                recipient_info.clone(),
+                token_mint_info.clone(),
+                destination_info.clone(),
+                owner_info.clone(),
+                token_program_info.clone(),
+            ],
+            &[&[receiver::RECIPIENT_OWNER_SEED, &[bump_seed]]],
+        )?;
+        msg!(
+            "Received {} from 0x{} for {}",
+            receive.amount,
+            hex::encode(&receive.sender),
+            wallet
+        );
+
+        Ok(())
+    }
 }
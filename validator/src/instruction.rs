@@ -22,6 +22,8 @@ pub enum GovernanceAddinInstruction {
     ///   8. `[signer]` Payer
     ///   9. `[]` System program
     ///   10+ Any extra accounts that are part of TokenOwnerRecord of the voter, in order
+    ///   Then the cToken account, followed by the cToken route of the source
+    ///      chain when it isn't the cToken destination
     SubmitVotes { data: Vec<u8> },
     // TODO: add process_upsert_votes_data instr to store votes data in the PDA account
 }
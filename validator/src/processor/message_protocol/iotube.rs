@@ -1,7 +1,10 @@
 use {
     super::MessageParser,
     borsh::{BorshDeserialize, BorshSerialize},
-    ctoken::{instruction::CTokenInstruction, state::CToken},
+    ctoken::{
+        instruction::{CTokenInstruction, SettleEntry},
+        state::{CToken, Route, SettlementReceipt, TransferId},
+    },
     solana_program::{
         account_info::AccountInfo, keccak::hash, program_error::ProgramError, pubkey::Pubkey,
     },
@@ -26,8 +29,7 @@ impl<'a> MessageParser<'a> for IoTubeProtocol<'a> {
             if &self.record_id()? != first.as_slice() {
                 return Err(ProgramError::InvalidAccountData);
             }
-            let payload = Payload::try_from_slice(self.raw_data)?;
-            payload.validate(proposal)?;
+            Message::parse(self.raw_data)?.validate(proposal)?;
             return Ok(());
         }
         return Err(ProgramError::InvalidAccountData);
@@ -47,13 +49,17 @@ impl<'a> MessageParser<'a> for IoTubeProtocol<'a> {
     fn instructions_from_proposal(
         &self,
         proposal_instruction: &Vec<InstructionData>,
-        ctoken_infos: &[AccountInfo], // accountinfo for co_token & co_token_programID(later one to be removed)
+        ctoken_infos: &[AccountInfo], // co_token, then the route of the source chain if any
     ) -> Result<Vec<InstructionData>, ProgramError> {
-        let payload = Payload::try_from_slice(self.raw_data)?;
-        if ctoken_infos.len() != 1 || *ctoken_infos[0].key != payload.co_token {
+        let message = Message::parse(self.raw_data)?;
+        if ctoken_infos.is_empty()
+            || ctoken_infos.len() > 2
+            || *ctoken_infos[0].key != message.co_token()
+        {
             return Err(ProgramError::InvalidAccountData);
         }
-        let c_token = CToken::try_from_slice(&ctoken_infos[0].data.borrow())?;
+        let c_token = CToken::unpack(&ctoken_infos[0].data.borrow())?;
+        check_source_chain(message.source_chain(&c_token), &c_token, ctoken_infos)?;
         let authority =
             Pubkey::find_program_address(&[&ctoken_infos[0].key.to_bytes()], ctoken_infos[0].owner)
                 .0;
@@ -62,61 +68,225 @@ impl<'a> MessageParser<'a> for IoTubeProtocol<'a> {
         }
         let mut new_instrs = proposal_instruction.clone();
         for instruction in new_instrs.iter_mut() {
-            if let CTokenInstruction::Settle { .. } =
-                CTokenInstruction::try_from_slice(&instruction.data)?
-            {
-                if instruction.accounts.len() != 8 {
-                    return Err(ProgramError::InvalidAccountData);
+            if instruction.program_id != *ctoken_infos[0].owner {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            match CTokenInstruction::try_from_slice(&instruction.data)? {
+                CTokenInstruction::SettleBatch { .. } => {
+                    let Message::Batch(payload) = &message else {
+                        return Err(ProgramError::InvalidAccountData);
+                    };
+                    settle_batch_from_payload(
+                        instruction,
+                        payload,
+                        &c_token,
+                        ctoken_infos[0].owner,
+                        &authority,
+                    )?;
                 }
-                instruction.data = CTokenInstruction::Settle {
-                    amount: payload.amount,
+                instruction_data @ (CTokenInstruction::Settle { .. }
+                | CTokenInstruction::SettleAndCall { .. }) => {
+                    let Some(payload) = message.single(&c_token) else {
+                        return Err(ProgramError::InvalidAccountData);
+                    };
+                    // the payload decides whether the receiver is called, and
+                    // the recipient owner which receiver
+                    let min_accounts = match instruction_data {
+                        CTokenInstruction::Settle { .. } if payload.payload.is_empty() => 12,
+                        CTokenInstruction::SettleAndCall { .. } if !payload.payload.is_empty() => {
+                            14
+                        }
+                        _ => return Err(ProgramError::InvalidInstructionData),
+                    };
+                    if instruction.accounts.len() < min_accounts {
+                        return Err(ProgramError::InvalidAccountData);
+                    }
+                    let transfer_id = TransferId {
+                        source_chain: payload.source_chain,
+                        cashier: payload.cashier,
+                        index: payload.index,
+                    };
+                    let receipt = SettlementReceipt::find_address(
+                        ctoken_infos[0].owner,
+                        &c_token.config,
+                        &transfer_id,
+                    )
+                    .0;
+                    instruction.data = match instruction_data {
+                        CTokenInstruction::Settle { .. } => CTokenInstruction::Settle {
+                            amount: payload.amount,
+                            transfer_id,
+                        },
+                        _ => CTokenInstruction::SettleAndCall {
+                            amount: payload.amount,
+                            transfer_id,
+                            sender: payload.sender.as_bytes().to_vec(),
+                            payload: payload.payload.clone(),
+                        },
+                    }
+                    .try_to_vec()?;
+                    instruction.accounts[0] = AccountMetaData {
+                        pubkey: payload.co_token,
+                        is_signer: false,
+                        is_writable: true,
+                    };
+                    instruction.accounts[1] = AccountMetaData {
+                        pubkey: authority,
+                        is_signer: false,
+                        is_writable: false,
+                    };
+                    instruction.accounts[2] = AccountMetaData {
+                        pubkey: c_token.token,
+                        is_signer: false,
+                        is_writable: true,
+                    };
+                    instruction.accounts[3] = AccountMetaData {
+                        pubkey: payload.recipient,
+                        is_signer: false,
+                        is_writable: true,
+                    };
+                    instruction.accounts[5] = AccountMetaData {
+                        pubkey: c_token.token_mint,
+                        is_signer: false,
+                        is_writable: true,
+                    };
+                    instruction.accounts[7] = AccountMetaData {
+                        pubkey: c_token.config,
+                        is_signer: false,
+                        is_writable: false,
+                    };
+                    instruction.accounts[8] = AccountMetaData {
+                        pubkey: receipt,
+                        is_signer: false,
+                        is_writable: true,
+                    };
                 }
-                .try_to_vec()?;
-                instruction.accounts[0] = AccountMetaData {
-                    pubkey: payload.co_token,
-                    is_signer: false,
-                    is_writable: false,
-                };
-                instruction.accounts[1] = AccountMetaData {
-                    pubkey: authority,
-                    is_signer: false,
-                    is_writable: false,
-                };
-                instruction.accounts[2] = AccountMetaData {
-                    pubkey: c_token.token,
-                    is_signer: false,
-                    is_writable: true,
-                };
-                instruction.accounts[3] = AccountMetaData {
-                    pubkey: payload.recipient,
-                    is_signer: false,
-                    is_writable: true,
-                };
-                instruction.accounts[5] = AccountMetaData {
-                    pubkey: c_token.token_mint,
-                    is_signer: false,
-                    is_writable: true,
-                };
-                instruction.accounts[7] = AccountMetaData {
-                    pubkey: c_token.config,
-                    is_signer: false,
-                    is_writable: false,
-                };
+                // the governance signs as the config authority, so no other
+                // instruction may ride along with witness approved transfers
+                _ => return Err(ProgramError::InvalidInstructionData),
             }
         }
         Ok(new_instrs)
     }
 }
+/// Checks that transfers from `source_chain` reach the cToken, either as its
+/// own destination or over the route passed after it
+fn check_source_chain(
+    source_chain: u32,
+    c_token: &CToken,
+    ctoken_infos: &[AccountInfo],
+) -> Result<(), ProgramError> {
+    if source_chain == c_token.destination {
+        return Ok(());
+    }
+    let route_info = ctoken_infos
+        .get(1)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    let (route, _) = Route::find_address(ctoken_infos[0].owner, ctoken_infos[0].key, source_chain);
+    if route_info.owner != ctoken_infos[0].owner || *route_info.key != route {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Route::unpack(&route_info.data.borrow())?;
+    Ok(())
+}
+/// Rewrites a `SettleBatch` proposal instruction into the transfers of a
+/// batch payload. Recipients are numbered in order of first appearance. The
+/// token and blocked accounts of the proposal recipients, and its associated
+/// token program and rent vault, are kept, as the token program checks them
+/// against the recipients.
+fn settle_batch_from_payload(
+    instruction: &mut InstructionData,
+    payload: &BatchPayload,
+    c_token: &CToken,
+    ctoken_program_id: &Pubkey,
+    authority: &Pubkey,
+) -> Result<(), ProgramError> {
+    let mut recipients: Vec<Pubkey> = vec![];
+    let mut entries = vec![];
+    for transfer in payload.transfers.iter() {
+        let recipient_index = match recipients.iter().position(|r| *r == transfer.recipient) {
+            Some(index) => index,
+            None => {
+                recipients.push(transfer.recipient);
+                recipients.len() - 1
+            }
+        };
+        entries.push(SettleEntry {
+            recipient_index: u8::try_from(recipient_index)
+                .map_err(|_| ProgramError::InvalidAccountData)?,
+            amount: transfer.amount,
+            transfer_id: TransferId {
+                source_chain: payload.source_chain,
+                cashier: payload.cashier,
+                index: transfer.index,
+            },
+        });
+    }
+    let receipts_start = 11;
+    let recipients_start = receipts_start + entries.len();
+    if instruction.accounts.len() < recipients_start + recipients.len() * 3 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    instruction.accounts[0] = AccountMetaData {
+        pubkey: payload.co_token,
+        is_signer: false,
+        is_writable: true,
+    };
+    instruction.accounts[1] = AccountMetaData {
+        pubkey: *authority,
+        is_signer: false,
+        is_writable: false,
+    };
+    instruction.accounts[2] = AccountMetaData {
+        pubkey: c_token.token,
+        is_signer: false,
+        is_writable: true,
+    };
+    instruction.accounts[4] = AccountMetaData {
+        pubkey: c_token.token_mint,
+        is_signer: false,
+        is_writable: true,
+    };
+    instruction.accounts[6] = AccountMetaData {
+        pubkey: c_token.config,
+        is_signer: false,
+        is_writable: false,
+    };
+    for (i, entry) in entries.iter().enumerate() {
+        instruction.accounts[receipts_start + i] = AccountMetaData {
+            pubkey: SettlementReceipt::find_address(
+                ctoken_program_id,
+                &c_token.config,
+                &entry.transfer_id,
+            )
+            .0,
+            is_signer: false,
+            is_writable: true,
+        };
+    }
+    for (i, recipient) in recipients.iter().enumerate() {
+        instruction.accounts[recipients_start + i * 3] = AccountMetaData {
+            pubkey: *recipient,
+            is_signer: false,
+            is_writable: true,
+        };
+    }
+    instruction.data = CTokenInstruction::SettleBatch { entries }.try_to_vec()?;
+    Ok(())
+}
 const ETH_ADDRESS_SIZE: usize = 20;
 #[derive(Clone, Debug, BorshDeserialize, BorshSerialize)]
 struct Payload {
     pub proposal: Pubkey,
     pub cashier: [u8; ETH_ADDRESS_SIZE],
     pub co_token: Pubkey,
+    /// Chain the transfer comes from, the cToken destination or a route
+    pub source_chain: u32,
     pub index: u64,
     pub sender: String,
     pub recipient: Pubkey,
-    pub amount: u64,
+    /// Amount in remote decimals, as settled by the cToken
+    pub amount: u128,
     pub payload: Vec<u8>,
 }
 impl Payload {
@@ -130,3 +300,124 @@ impl Payload {
         Ok(())
     }
 }
+#[derive(Clone, Debug, BorshDeserialize, BorshSerialize)]
+struct BatchTransfer {
+    pub index: u64,
+    pub sender: String,
+    pub recipient: Pubkey,
+    /// Amount in remote decimals, as settled by the cToken
+    pub amount: u128,
+    pub payload: Vec<u8>,
+}
+/// Transfers of one cashier and cToken approved by the witnesses together
+#[derive(Clone, Debug, BorshDeserialize, BorshSerialize)]
+struct BatchPayload {
+    pub proposal: Pubkey,
+    pub cashier: [u8; ETH_ADDRESS_SIZE],
+    pub co_token: Pubkey,
+    /// Chain the transfers come from, the cToken destination or a route
+    pub source_chain: u32,
+    pub transfers: Vec<BatchTransfer>,
+}
+impl BatchPayload {
+    fn validate(&self, proposal: &Pubkey) -> Result<(), ProgramError> {
+        if self.proposal != *proposal {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if self.transfers.is_empty() || self.transfers.iter().any(|t| t.amount == 0) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        // batches can't call receivers
+        if self.transfers.iter().any(|t| !t.payload.is_empty()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+}
+/// Single transfer of witnesses predating the message kind byte, with the
+/// amount as u64 and the cToken destination as source chain
+#[derive(Clone, Debug, BorshDeserialize, BorshSerialize)]
+struct LegacyPayload {
+    pub proposal: Pubkey,
+    pub cashier: [u8; ETH_ADDRESS_SIZE],
+    pub co_token: Pubkey,
+    pub index: u64,
+    pub sender: String,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub payload: Vec<u8>,
+}
+impl LegacyPayload {
+    fn to_payload(&self, source_chain: u32) -> Payload {
+        Payload {
+            proposal: self.proposal,
+            cashier: self.cashier,
+            co_token: self.co_token,
+            source_chain,
+            index: self.index,
+            sender: self.sender.clone(),
+            recipient: self.recipient,
+            amount: self.amount.into(),
+            payload: self.payload.clone(),
+        }
+    }
+}
+/// Message kind byte of a single transfer
+const SINGLE_KIND: u8 = 0;
+/// Message kind byte of a batch
+const BATCH_KIND: u8 = 1;
+/// Witness approved message, a single transfer or a batch, Borsh encoded
+/// after a kind byte. Data that doesn't decode after a kind byte is a bare
+/// `LegacyPayload`, so old and new witnesses can run side by side.
+enum Message {
+    Single(Payload),
+    Batch(BatchPayload),
+    Legacy(LegacyPayload),
+}
+impl Message {
+    fn parse(raw_data: &[u8]) -> Result<Self, ProgramError> {
+        match raw_data.split_first() {
+            Some((&SINGLE_KIND, data)) => {
+                if let Ok(payload) = Payload::try_from_slice(data) {
+                    return Ok(Message::Single(payload));
+                }
+            }
+            Some((&BATCH_KIND, data)) => {
+                if let Ok(payload) = BatchPayload::try_from_slice(data) {
+                    return Ok(Message::Batch(payload));
+                }
+            }
+            _ => {}
+        }
+        Ok(Message::Legacy(LegacyPayload::try_from_slice(raw_data)?))
+    }
+    fn co_token(&self) -> Pubkey {
+        match self {
+            Message::Single(payload) => payload.co_token,
+            Message::Batch(payload) => payload.co_token,
+            Message::Legacy(payload) => payload.co_token,
+        }
+    }
+    fn source_chain(&self, c_token: &CToken) -> u32 {
+        match self {
+            Message::Single(payload) => payload.source_chain,
+            Message::Batch(payload) => payload.source_chain,
+            Message::Legacy(_) => c_token.destination,
+        }
+    }
+    /// Single transfer of the message, if it isn't a batch
+    fn single(&self, c_token: &CToken) -> Option<Payload> {
+        match self {
+            Message::Single(payload) => Some(payload.clone()),
+            Message::Batch(_) => None,
+            Message::Legacy(payload) => Some(payload.to_payload(c_token.destination)),
+        }
+    }
+    fn validate(&self, proposal: &Pubkey) -> Result<(), ProgramError> {
+        match self {
+            Message::Single(payload) => payload.validate(proposal),
+            Message::Batch(payload) => payload.validate(proposal),
+            Message::Legacy(payload) => payload.to_payload(0).validate(proposal),
+        }
+    }
+}
@@ -141,7 +141,7 @@ pub fn process_submit_votes(
     )?;
     let record_instruction = message_parser.instructions_from_proposal(
         &proposal_transaction_data.instructions,
-        next_account_infos(account_info_iter, 1)?,
+        account_info_iter.as_slice(),
     )?;
     let record_transaction_data = RecordTransaction {
         account_type: GovernanceAddinAccountType::RecordTransaction,